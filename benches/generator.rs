@@ -0,0 +1,31 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use pvss::crypto::{Point, Scalar};
+use pvss::math::Polynomial;
+
+fn generator(c: &mut Criterion) {
+    let s = Scalar::generate();
+
+    let mut group = c.benchmark_group("generators");
+    group.bench_function("generator", |b| b.iter(Point::generator));
+    group.bench_function("generator_h", |b| b.iter(Point::generator_h));
+    group.finish();
+
+    let g = Point::generator();
+    let mut group = c.benchmark_group("generator * scalar");
+    group.bench_function("mul", |b| b.iter(|| g.mul(&s)));
+    group.bench_function("from_scalar (table)", |b| b.iter(|| Point::from_scalar(&s)));
+    group.bench_function("mul_base", |b| b.iter(|| Point::mul_base(&s)));
+    group.bench_function("double", |b| b.iter(|| g.double()));
+    group.finish();
+
+    // the commitments of a dealer to a degree-100 polynomial
+    let poly = Polynomial::generate(100);
+    let mut group = c.benchmark_group("commit degree 100");
+    group.sample_size(20);
+    group.bench_function("commit", |b| b.iter(|| poly.commit(&g)));
+    group.bench_function("commit_generator", |b| b.iter(|| poly.commit_generator()));
+    group.finish();
+}
+
+criterion_group!(benches, generator);
+criterion_main!(benches);
@@ -0,0 +1,26 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use pvss::crypto::{Point, Scalar};
+
+fn msm(c: &mut Criterion) {
+    let points: Vec<Point> = (0..100)
+        .map(|_| Point::from_scalar(&Scalar::generate()))
+        .collect();
+    let scalars: Vec<Scalar> = (0..100).map(|_| Scalar::generate()).collect();
+
+    let mut group = c.benchmark_group("sum of 100 products");
+    group.bench_function("naive", |b| {
+        b.iter(|| {
+            points
+                .iter()
+                .zip(scalars.iter())
+                .fold(Point::infinity(), |acc, (p, s)| acc + p.mul(s))
+        })
+    });
+    group.bench_function("multi_scalar_mul", |b| {
+        b.iter(|| Point::multi_scalar_mul(&points, &scalars))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, msm);
+criterion_main!(benches);
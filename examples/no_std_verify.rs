@@ -0,0 +1,38 @@
+// dealing and verification from a `no_std` crate, which only needs `alloc`
+// and an rng supplied by the caller. check that it builds with
+//
+//     cargo build --example no_std_verify --no-default-features
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use pvss::crypto::{self, PublicKey};
+use pvss::pvss::{decrypt_share, reconstruct, Dealer};
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+/// deal a secret derived from `seed` to `n` fresh participants and check the
+/// dealing and its reconstruction
+pub fn deal_and_verify(seed: [u8; 32], threshold: usize, n: usize) -> bool {
+    let mut rng = ChaCha20Rng::from_seed(seed);
+    let keys: Vec<_> = (0..n).map(|_| crypto::create_keypair_with(&mut rng)).collect();
+    let pks: Vec<PublicKey> = keys.iter().map(|(pk, _)| pk.clone()).collect();
+    let secret = crypto::Scalar::random_nonzero(&mut rng);
+    let dealing = match Dealer::deal(&secret, &pks, threshold, &mut rng) {
+        Ok(dealing) => dealing,
+        Err(_) => return false,
+    };
+    if !dealing.verify(&pks) {
+        return false;
+    }
+    let decrypted: Vec<_> = keys
+        .iter()
+        .zip(dealing.shares.iter())
+        .map(|((_, sk), share)| (share.index, decrypt_share(sk, share)))
+        .collect();
+    match (reconstruct(&decrypted, threshold), dealing.public_commitment()) {
+        (Ok(recovered), Ok(expected)) => recovered == expected,
+        _ => false,
+    }
+}
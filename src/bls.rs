@@ -0,0 +1,49 @@
+// BLS signatures with the keys of the library: the public key is in G1, so
+// messages are hashed to G2 and signatures are points of G2
+// with a non-standard try-and-increment hash, so signatures do not verify with
+// other BLS implementations
+use alloc::vec::Vec;
+
+use super::crypto::*;
+use super::error::PvssError;
+
+// domain separation tag of the message hash
+const SIGNATURE_DST: &[u8] = b"PVSS-BLS12381-BLS-SIG-V1";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Signature {
+    point: PointG2,
+}
+
+impl Signature {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.point.to_bytes()
+    }
+
+    /// checks that the point is in the prime order subgroup of G2
+    pub fn from_bytes(bytes: &[u8]) -> Result<Signature, PvssError> {
+        Ok(Signature {
+            point: PointG2::try_from_bytes(bytes)?,
+        })
+    }
+}
+
+fn hash_message(msg: &[u8]) -> PointG2 {
+    PointG2::hash_to_curve_try_and_increment(msg, SIGNATURE_DST)
+}
+
+/// `H(msg) * sk`
+pub fn sign(sk: &PrivateKey, msg: &[u8]) -> Signature {
+    Signature {
+        point: hash_message(msg).mul(&sk.scalar),
+    }
+}
+
+/// check that `e(pk, H(msg)) == e(G, sig)`. the identity is not a valid
+/// public key: every signature of it is the identity, whatever the message.
+pub fn verify(pk: &PublicKey, msg: &[u8], sig: &Signature) -> bool {
+    if pk.point.is_identity() {
+        return false;
+    }
+    pairing(&pk.point, &hash_message(msg)) == pairing(&Point::generator(), &sig.point)
+}
@@ -1,233 +1,1869 @@
 use ark_bls12_381::Bls12_381;
+use ark_ec::models::short_weierstrass_jacobian::{GroupAffine, GroupProjective};
+use ark_ec::models::SWModelParameters;
+use ark_ec::msm::{FixedBaseMSM, VariableBaseMSM};
 use ark_ec::{AffineCurve, PairingEngine, ProjectiveCurve};
-use ark_ff::{Field, FromBytes, ToBytes, Zero};
+use ark_ff::{batch_inversion, Field, FromBytes, One, PrimeField, ToBytes, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+use ark_serialize::{CanonicalDeserializeWithFlags, CanonicalSerializeWithFlags, SWFlags};
 use ark_std::UniformRand;
-use std::ops::Add;
-use std::ops::Mul;
-use std::ops::Sub;
-use rand::{SeedableRng, rngs::StdRng};
-use ::cp::sha2::Sha256;
-use ::cp::digest::Digest;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use ark_std::io::{self, Read, Write};
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::{Product, Sum};
+use core::mem;
+use core::ops::Add;
+use core::ops::AddAssign;
+use core::ops::Div;
+use core::ops::DivAssign;
+use core::ops::Mul;
+use core::ops::MulAssign;
+use core::ops::Neg;
+use core::ops::Sub;
+use core::ops::SubAssign;
+use once_cell::race::OnceBox;
+#[cfg(feature = "std")]
+use rand::{rngs::StdRng, SeedableRng};
+use rand::{CryptoRng, Rng, RngCore};
+use sha2::{Digest, Sha256};
+#[cfg(feature = "std")]
+use std::error;
 
-type Group381 = <Bls12_381 as PairingEngine>::G1Projective;
-type Affine381 = <Bls12_381 as PairingEngine>::G1Affine;
-type BigInt381 = <Bls12_381 as PairingEngine>::Fr;
+use super::super::error::PvssError;
 
-pub struct Scalar {
-    bn: BigInt381,
+/// A pairing friendly curve, on whose G1 group the scalars, points and keys
+/// are defined. G1 and G2 have to be short Weierstrass curves.
+pub trait Curve:
+    PairingEngine<
+    G1Affine = GroupAffine<<Self as Curve>::G1Parameters>,
+    G1Projective = GroupProjective<<Self as Curve>::G1Parameters>,
+    G2Affine = GroupAffine<<Self as Curve>::G2Parameters>,
+    G2Projective = GroupProjective<<Self as Curve>::G2Parameters>,
+>
+{
+    type G1Parameters: SWModelParameters<BaseField = Self::Fq, ScalarField = Self::Fr>;
+    type G2Parameters: SWModelParameters<BaseField = Self::Fqe, ScalarField = Self::Fr>;
+
+    /// domain separation tag of `Scalar::hash_points`
+    const HASH_POINTS_DST: &'static [u8];
+    /// domain separation tag hashed to the curve by `Point::generator_h`
+    const GENERATOR_H_DST: &'static [u8];
+    /// domain separation tag of `Scalar::generate_from_seed`
+    const KEYGEN_DST: &'static [u8];
+    /// domain separation tag of `Point::nums_generator`
+    const NUMS_DST: &'static [u8];
+
+    /// the generator of `Point::generator`, computed once
+    fn cached_generator() -> &'static Self::G1Affine;
+    /// the generator of `Point::generator_h`, hashed to the curve once
+    fn cached_generator_h() -> &'static Self::G1Affine;
+    /// the multiples of `Point::generator` used by `Point::from_scalar`
+    fn cached_generator_table() -> &'static GeneratorTable<Self>;
+    /// the hash to G1 of `Point::hash_to_curve`
+    fn hash_to_g1(msg: &[u8], dst: &[u8]) -> Self::G1Projective;
+}
+
+// the generators need a static per curve, and statics cannot be generic.
+// `OnceBox` only needs `alloc`; two threads racing on the first use may both
+// compute the value, and one of them is dropped.
+macro_rules! cached_generators {
+    ($curve:ty, $affine:ty) => {
+        fn cached_generator() -> &'static $affine {
+            static GENERATOR: OnceBox<$affine> = OnceBox::new();
+            GENERATOR.get_or_init(|| Box::new(curve_generator::<Self>().into_affine()))
+        }
+
+        fn cached_generator_h() -> &'static $affine {
+            static GENERATOR_H: OnceBox<$affine> = OnceBox::new();
+            GENERATOR_H.get_or_init(|| {
+                Box::new(Self::hash_to_g1(&[], Self::GENERATOR_H_DST).into_affine())
+            })
+        }
+
+        fn cached_generator_table() -> &'static GeneratorTable<$curve> {
+            static TABLE: OnceBox<GeneratorTable<$curve>> = OnceBox::new();
+            TABLE.get_or_init(|| Box::new(GeneratorTable::new(&Point::generator())))
+        }
+    };
+}
+
+impl Curve for Bls12_381 {
+    type G1Parameters = ark_bls12_381::g1::Parameters;
+    type G2Parameters = ark_bls12_381::g2::Parameters;
+
+    const HASH_POINTS_DST: &'static [u8] = b"PVSS-BLS12381-HASH-POINTS-V1";
+    const GENERATOR_H_DST: &'static [u8] = b"PVSS-BLS12381-GENERATOR-H-V1";
+    const KEYGEN_DST: &'static [u8] = b"PVSS-BLS12381-KEYGEN-V1";
+    const NUMS_DST: &'static [u8] = b"PVSS-BLS12381-NUMS-V1";
+
+    cached_generators!(Bls12_381, ark_bls12_381::G1Affine);
+
+    fn hash_to_g1(msg: &[u8], dst: &[u8]) -> Self::G1Projective {
+        super::hash_to_curve::hash_to_g1(msg, dst)
+    }
+}
+
+// also built for the tests, which run over both curves
+#[cfg(any(test, feature = "bn254"))]
+impl Curve for ark_bn254::Bn254 {
+    type G1Parameters = ark_bn254::g1::Parameters;
+    type G2Parameters = ark_bn254::g2::Parameters;
+
+    const HASH_POINTS_DST: &'static [u8] = b"PVSS-BN254-HASH-POINTS-V1";
+    const GENERATOR_H_DST: &'static [u8] = b"PVSS-BN254-GENERATOR-H-V1";
+    const KEYGEN_DST: &'static [u8] = b"PVSS-BN254-KEYGEN-V1";
+    const NUMS_DST: &'static [u8] = b"PVSS-BN254-NUMS-V1";
+
+    cached_generators!(ark_bn254::Bn254, ark_bn254::G1Affine);
+
+    // RFC 9380 has no SSWU suite for BN254, whose curve has `A = 0` and no
+    // isogeny of the RFC: this keeps the non-standard try-and-increment map
+    fn hash_to_g1(msg: &[u8], dst: &[u8]) -> Self::G1Projective {
+        try_and_increment_g1::<Self>(msg, dst)
+    }
+}
+
+// size of the encodings produced by `Scalar::to_bytes` and `Point::to_bytes`,
+// which write the limbs of the field elements
+fn scalar_bytes<E: Curve>() -> usize {
+    mem::size_of::<<E::Fr as PrimeField>::BigInt>()
+}
+
+fn point_bytes<E: Curve>() -> usize {
+    2 * mem::size_of::<<E::Fq as PrimeField>::BigInt>() + 1
+}
+
+fn point_g2_bytes<E: Curve>() -> usize {
+    let mut buf: Vec<u8> = Vec::new();
+    E::G2Affine::zero().write(&mut buf).expect("");
+    buf.len()
+}
+
+// bytes of hash output reduced into a scalar; 64 bytes keep the bias of the
+// reduction negligible (RFC 9380, section 5)
+const HASH_TO_SCALAR_BYTES: usize = 64;
+
+/// Errors raised when decoding a scalar, a point or a key from bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// the input is not the size of an encoded value
+    InvalidLength,
+    /// the input does not encode a scalar of the field
+    InvalidScalar,
+    /// the input does not encode the coordinates of a point
+    InvalidPoint,
+    /// the coordinates do not satisfy the curve equation
+    NotOnCurve,
+    /// the input encodes a point outside of the prime order subgroup
+    NotInSubgroup,
+    /// the input is not an even number of hex digits
+    InvalidHex,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::InvalidLength => write!(f, "invalid encoding length"),
+            DecodeError::InvalidScalar => write!(f, "invalid scalar encoding"),
+            DecodeError::InvalidPoint => write!(f, "invalid point encoding"),
+            DecodeError::NotOnCurve => write!(f, "point not on the curve"),
+            DecodeError::NotInSubgroup => write!(f, "point not in the prime order subgroup"),
+            DecodeError::InvalidHex => write!(f, "invalid hex string"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for DecodeError {}
+
+/// Errors raised when making a keypair
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyError {
+    /// the private key is zero, so the public key would be the identity
+    ZeroScalar,
+}
+
+impl fmt::Display for KeyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            KeyError::ZeroScalar => write!(f, "zero private key"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for KeyError {}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>, DecodeError> {
+    hex::decode(s).map_err(|_| DecodeError::InvalidHex)
+}
+
+pub(crate) fn point_error(e: DecodeError) -> PvssError {
+    match e {
+        DecodeError::NotOnCurve => PvssError::NotOnCurve,
+        DecodeError::NotInSubgroup => PvssError::NotInSubgroup,
+        _ => PvssError::InvalidPointEncoding,
+    }
+}
+
+fn scalar_error(_: DecodeError) -> PvssError {
+    PvssError::InvalidScalarEncoding
+}
+
+pub struct Scalar<E: Curve> {
+    bn: E::Fr,
 }
 
-pub struct Point {
-    point: Group381,
+pub struct Point<E: Curve> {
+    point: E::G1Projective,
 }
 
-#[derive(PartialEq)]
-pub struct PrivateKey {
-    pub scalar: Scalar,
+pub struct PrivateKey<E: Curve> {
+    pub scalar: Scalar<E>,
 }
 
-#[derive(PartialEq)]
-pub struct PublicKey {
-    pub point: Point,
+pub struct PublicKey<E: Curve> {
+    pub point: Point<E>,
 }
 
-impl PublicKey {
+impl<E: Curve> PublicKey<E> {
     pub fn to_bytes(&self) -> Vec<u8> {
         self.point.to_bytes()
     }
 
-    pub fn from_bytes(bytes: &[u8]) -> PublicKey {
+    pub fn from_bytes(bytes: &[u8]) -> Result<PublicKey<E>, DecodeError> {
+        let point = Point::from_bytes(bytes)?;
+        Ok(PublicKey { point })
+    }
+
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<PublicKey<E>, PvssError> {
+        Self::from_bytes(bytes).map_err(point_error)
+    }
+
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.to_bytes())
+    }
+
+    /// the same errors as `Point::from_hex`
+    pub fn from_hex(s: &str) -> Result<PublicKey<E>, DecodeError> {
+        Self::from_bytes(&decode_hex(s)?)
+    }
+
+    pub fn to_base64(&self) -> String {
+        base64::encode(self.to_bytes())
+    }
+
+    pub fn from_base64(s: &str) -> Result<PublicKey<E>, PvssError> {
+        let bytes = base64::decode(s).map_err(|_| PvssError::InvalidPointEncoding)?;
+        Self::try_from_bytes(&bytes)
+    }
+
+    /// the sum of the keys, the point at infinity for no keys. a plain sum is
+    /// open to rogue keys chosen after the others: check a `SchnorrProof` of
+    /// each key first, or use `aggregate_weighted` with weights the key owners
+    /// cannot predict
+    pub fn aggregate(keys: &[PublicKey<E>]) -> PublicKey<E> {
+        PublicKey {
+            point: keys.iter().map(|k| &k.point).sum(),
+        }
+    }
+
+    /// `sum(keys[i] * weights[i])`, for Lagrange or random coefficients.
+    /// panics if there are not as many weights as keys.
+    pub fn aggregate_weighted(keys: &[PublicKey<E>], weights: &[Scalar<E>]) -> PublicKey<E> {
+        let points: Vec<Point<E>> = keys.iter().map(|k| k.point.clone()).collect();
         PublicKey {
-            point: Point {
-                point: Affine381::read(bytes).expect("Could not create PublicKey from bytes").into_projective(),
-            },
+            point: Point::multi_scalar_mul(&points, weights),
         }
     }
 }
 
-impl PrivateKey {
+impl<E: Curve> PrivateKey<E> {
     pub fn to_bytes(&self) -> Vec<u8> {
         self.scalar.to_bytes()
     }
 
-    pub fn from_bytes(bytes: &[u8]) -> PrivateKey {
-        PrivateKey {
-            scalar: Scalar {
-                bn: BigInt381::read(bytes).expect("Could not create PublicKey from bytes"),
-            },
+    pub fn from_bytes(bytes: &[u8]) -> Result<PrivateKey<E>, DecodeError> {
+        let scalar = Scalar::from_bytes(bytes)?;
+        Ok(PrivateKey { scalar })
+    }
+
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<PrivateKey<E>, PvssError> {
+        Self::from_bytes(bytes).map_err(scalar_error)
+    }
+
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.to_bytes())
+    }
+
+    /// the same errors as `Scalar::from_hex`
+    pub fn from_hex(s: &str) -> Result<PrivateKey<E>, DecodeError> {
+        Self::from_bytes(&decode_hex(s)?)
+    }
+
+    pub fn to_base64(&self) -> String {
+        base64::encode(self.to_bytes())
+    }
+
+    pub fn from_base64(s: &str) -> Result<PrivateKey<E>, PvssError> {
+        let bytes = base64::decode(s).map_err(|_| PvssError::InvalidScalarEncoding)?;
+        Self::try_from_bytes(&bytes)
+    }
+}
+
+impl<E: Curve> PartialEq for PublicKey<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.point == other.point
+    }
+}
+
+impl<E: Curve> Eq for PublicKey<E> {}
+
+impl<E: Curve> Hash for PublicKey<E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.point.hash(state)
+    }
+}
+
+impl<E: Curve> Clone for PublicKey<E> {
+    fn clone(&self) -> PublicKey<E> {
+        PublicKey {
+            point: self.point.clone(),
         }
     }
 }
 
-pub fn create_keypair() -> (PublicKey, PrivateKey) {
-    let s = Scalar::generate();
+/// derive a keypair deterministically from a seed, see
+/// `Scalar::generate_from_seed`. this is for reproducible keys and test
+/// vectors: the seed is the private key in all but name, so it must be high
+/// entropy (32 bytes from a cryptographic rng, or a strong master secret)
+pub fn create_keypair_from_seed<E: Curve>(seed: &[u8; 32]) -> (PublicKey<E>, PrivateKey<E>) {
+    let s = Scalar::generate_from_seed(seed);
     let p = Point::from_scalar(&s);
     (PublicKey { point: p }, PrivateKey { scalar: s })
 }
 
-fn get_point_at_infinity() -> Group381 {
-    Group381::zero()
+#[cfg(feature = "std")]
+pub fn create_keypair<E: Curve>() -> (PublicKey<E>, PrivateKey<E>) {
+    create_keypair_with(&mut StdRng::from_entropy())
+}
+
+pub fn create_keypair_with<E: Curve, R: RngCore + CryptoRng>(
+    rng: &mut R,
+) -> (PublicKey<E>, PrivateKey<E>) {
+    create_keypair_with_rng(rng)
+}
+
+pub fn create_keypair_with_rng<E: Curve, R: Rng + ?Sized>(
+    rng: &mut R,
+) -> (PublicKey<E>, PrivateKey<E>) {
+    // a zero key has probability 1/r, but it would make every share
+    // encrypted to it the identity
+    let s = Scalar::rand_nonzero(rng);
+    (
+        PublicKey {
+            point: Point::from_scalar(&s),
+        },
+        PrivateKey { scalar: s },
+    )
+}
+
+/// the keypair with private key `s`, which must not be zero
+pub fn create_keypair_from_scalar<E: Curve>(
+    s: Scalar<E>,
+) -> Result<(PublicKey<E>, PrivateKey<E>), KeyError> {
+    let p = Point::from_scalar_checked(&s)?;
+    Ok((PublicKey { point: p }, PrivateKey { scalar: s }))
+}
+
+fn sha256(chunks: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    for chunk in chunks {
+        hasher.update(chunk);
+    }
+    hasher.finalize().into()
+}
+
+// expand_message_xmd from RFC 9380 (section 5.3.1), instantiated with SHA-256
+pub(crate) fn expand_message_xmd(msg: &[u8], dst: &[u8], len: usize) -> Vec<u8> {
+    let ell = len.div_ceil(32);
+    assert!(ell <= 255 && len <= 0xffff, "expand_message_xmd: output too long");
+    assert!(dst.len() <= 255, "expand_message_xmd: dst too long");
+
+    let dst_prime = [dst, &[dst.len() as u8]].concat();
+    let z_pad = [0u8; 64];
+    let l_i_b_str = (len as u16).to_be_bytes();
+
+    let b_0 = sha256(&[&z_pad, msg, &l_i_b_str, &[0], &dst_prime]);
+    let mut b_i = sha256(&[&b_0, &[1], &dst_prime]);
+    let mut out = Vec::with_capacity(ell * 32);
+    out.extend_from_slice(&b_i);
+    for i in 2..=ell {
+        let mut xored = [0u8; 32];
+        for j in 0..32 {
+            xored[j] = b_0[j] ^ b_i[j];
+        }
+        b_i = sha256(&[&xored, &[i as u8], &dst_prime]);
+        out.extend_from_slice(&b_i);
+    }
+    out.truncate(len);
+    out
+}
+
+// hash to a point of the prime order subgroup by try-and-increment, for BN254:
+// the message and a counter are hashed to a base field element until it is the
+// x coordinate of a curve point, then the cofactor is cleared. nobody knows the
+// discrete log of the result relative to any other point. this is not a map of
+// RFC 9380: the outputs differ from standard implementations, and the number of
+// attempts, hence the running time, depends on `msg`.
+#[cfg(any(test, feature = "bn254"))]
+fn try_and_increment_g1<E: Curve>(msg: &[u8], dst: &[u8]) -> E::G1Projective {
+    for counter in 0..=255u8 {
+        let uniform = expand_message_xmd(&[msg, &[counter]].concat(), dst, 64);
+        let x = E::Fq::from_be_bytes_mod_order(&uniform);
+        if let Some(p) = E::G1Affine::get_point_from_x(x, false) {
+            let p = p.mul_by_cofactor_to_projective();
+            if !p.is_zero() {
+                return p;
+            }
+        }
+    }
+    panic!("try and increment: no point found")
+}
+
+// `try_and_increment_g1` on G2, whose base field is an extension: the uniform bytes
+// are split between its coefficients, and values out of range are skipped
+fn try_and_increment_g2<E: Curve>(msg: &[u8], dst: &[u8]) -> E::G2Projective {
+    let len = 2 * E::Fqe::zero().serialized_size();
+    for counter in 0..=255u8 {
+        let uniform = expand_message_xmd(&[msg, &[counter]].concat(), dst, len);
+        let x = match E::Fqe::from_random_bytes(&uniform) {
+            Some(x) => x,
+            None => continue,
+        };
+        if let Some(p) = E::G2Affine::get_point_from_x(x, false) {
+            let p = p.mul_by_cofactor_to_projective();
+            if !p.is_zero() {
+                return p;
+            }
+        }
+    }
+    panic!("try and increment: no point found")
+}
+
+fn get_point_at_infinity<E: Curve>() -> E::G1Projective {
+    E::G1Projective::zero()
 }
 
-fn curve_generator() -> Group381 {
-    Group381::rand(&mut StdRng::seed_from_u64(42))
+// the standard generator of the curve (for BLS12-381, the one of the
+// specification). changing the base point changes every derived public key,
+// commitment and proof, so it is a breaking change.
+fn curve_generator<E: Curve>() -> E::G1Projective {
+    E::G1Projective::prime_subgroup_generator()
 }
 
-impl Scalar {
-    pub fn from_u32(v: u32) -> Scalar {
+impl<E: Curve> Scalar<E> {
+    pub fn from_u32(v: u32) -> Scalar<E> {
+        Scalar { bn: E::Fr::from(v) }
+    }
+
+    #[cfg(feature = "std")]
+    pub fn generate() -> Scalar<E> {
+        Self::rand(&mut StdRng::from_entropy())
+    }
+
+    pub fn generate_with<R: RngCore + CryptoRng>(rng: &mut R) -> Scalar<E> {
+        Self::rand(rng)
+    }
+
+    /// a uniform scalar drawn from `rng`. secrets need a cryptographic rng,
+    /// which `generate_with` enforces; this also takes deterministic ones.
+    /// arkworks draws as many random bits as the modulus has and starts over
+    /// when they are not below it, so there is no modular bias.
+    pub fn rand<R: Rng + ?Sized>(rng: &mut R) -> Scalar<E> {
         Scalar {
-            bn: BigInt381::from(v),
+            bn: E::Fr::rand(rng),
+        }
+    }
+
+    /// a uniform nonzero scalar, e.g. for blinding factors or keys. a draw is
+    /// zero with probability 1/r, so the loop ends almost surely.
+    pub fn random_nonzero<R: RngCore + CryptoRng>(rng: &mut R) -> Scalar<E> {
+        Self::rand_nonzero(rng)
+    }
+
+    fn rand_nonzero<R: Rng + ?Sized>(rng: &mut R) -> Scalar<E> {
+        loop {
+            let s = Self::rand(rng);
+            if !s.is_zero() {
+                return s;
+            }
         }
     }
 
-    pub fn generate() -> Scalar {
+    /// hash the seed to a scalar, as `hash_to_field` of RFC 9380, so that the
+    /// same seed always gives the same scalar
+    pub fn generate_from_seed(seed: &[u8; 32]) -> Scalar<E> {
+        let uniform = expand_message_xmd(seed, E::KEYGEN_DST, HASH_TO_SCALAR_BYTES);
         Scalar {
-            bn: BigInt381::rand(&mut StdRng::from_entropy()),
+            bn: E::Fr::from_be_bytes_mod_order(&uniform),
         }
     }
 
-    pub fn multiplicative_identity() -> Scalar {
+    pub fn zero() -> Scalar<E> {
+        Scalar { bn: E::Fr::zero() }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.bn.is_zero()
+    }
+
+    pub fn multiplicative_identity() -> Scalar<E> {
         Self::from_u32(1)
     }
 
-    pub fn hash_points(points: Vec<Point>) -> Scalar {
-        let mut data = Vec::new();
-        for p in points {
-            data.extend_from_slice(p.to_bytes().as_slice());
+    pub fn hash_points(points: Vec<Point<E>>) -> Scalar<E> {
+        Self::hash_points_with_dst(&points, E::HASH_POINTS_DST)
+    }
+
+    /// hash points to a scalar with `hash_to_scalar`: the encoded points are
+    /// expanded with `expand_message_xmd` and reduced modulo the group order
+    pub fn hash_points_with_dst(points: &[Point<E>], dst: &[u8]) -> Scalar<E> {
+        Self::hash_to_scalar(&Point::batch_to_bytes(points).concat(), dst)
+    }
+
+    /// hash bytes to a scalar: 64 bytes of `expand_message_xmd`, read as a
+    /// little endian integer and reduced modulo the group order. this follows
+    /// `hash_to_field` of RFC 9380 except for the byte order, which is big
+    /// endian in the RFC.
+    pub fn hash_to_scalar(msg: &[u8], dst: &[u8]) -> Scalar<E> {
+        let uniform = expand_message_xmd(msg, dst, HASH_TO_SCALAR_BYTES);
+        Scalar {
+            bn: E::Fr::from_le_bytes_mod_order(&uniform),
+        }
+    }
+
+    pub fn pow(&self, pow: u32) -> Scalar<E> {
+        self.pow_u64(pow as u64)
+    }
+
+    pub fn pow_u64(&self, pow: u64) -> Scalar<E> {
+        Scalar {
+            bn: self.bn.pow([pow]),
         }
-        let mut hasher = Sha256::new();
-        hasher.input(data.as_slice());
-        let mut dig: [u8; 32] = [0; 32];
-        hasher.result(&mut dig);
+    }
+
+    /// `self` to the power of the integer `exp`, from 0 to r - 1, by square
+    /// and multiply over its bits
+    pub fn pow_scalar(&self, exp: &Scalar<E>) -> Scalar<E> {
         Scalar {
-            bn: BigInt381::rand(&mut StdRng::from_seed(dig)),
+            bn: self.bn.pow(exp.bn.into_repr()),
         }
     }
 
-    pub fn pow(&self, pow: u32) -> Scalar {
+    pub fn square(&self) -> Scalar<E> {
         Scalar {
-            bn: self.bn.pow([pow as u64]),
+            bn: self.bn.square(),
         }
     }
 
-    pub fn inverse(&self) -> Scalar {
+    pub fn inverse(&self) -> Scalar<E> {
         Scalar {
             bn: self.bn.inverse().expect("Inverse failure"),
         }
     }
 
+    /// `self / rhs`, or `None` when `rhs` is zero
+    pub fn checked_div(&self, rhs: &Scalar<E>) -> Option<Scalar<E>> {
+        rhs.bn.inverse().map(|inv| Scalar { bn: self.bn * inv })
+    }
+
+    /// invert every scalar with Montgomery's trick: a single field inversion
+    /// and 3(n-1) multiplications. zero has no inverse and is returned as zero.
+    pub fn batch_inverse(scalars: &[Scalar<E>]) -> Vec<Scalar<E>> {
+        let mut bns: Vec<E::Fr> = scalars.iter().map(|s| s.bn).collect();
+        batch_inversion(&mut bns);
+        bns.into_iter().map(|bn| Scalar { bn }).collect()
+    }
+
+    /// the arkworks encoding of the scalar. its length follows the field
+    /// representation of the curve; wire protocols should prefer
+    /// `to_bytes_canonical`.
     pub fn to_bytes(&self) -> Vec<u8> {
-        let mut buf: Vec<u8> = Vec::new();
-        self.bn.write(&mut buf).expect("");
+        let mut buf: Vec<u8> = Vec::with_capacity(scalar_bytes::<E>());
+        self.write_to(&mut buf).expect("");
         buf
     }
 
-    pub fn from_bytes(bytes: &[u8]) -> Self {
+    /// write `to_bytes` directly to `w`
+    pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        self.bn.write(w)
+    }
+
+    /// read a scalar written by `write_to`, consuming exactly its length from `r`
+    pub fn read_from<R: Read>(r: &mut R) -> Result<Scalar<E>, PvssError> {
+        let bn = E::Fr::read(r).map_err(|_| PvssError::InvalidScalarEncoding)?;
+        Ok(Scalar { bn })
+    }
+
+    /// the canonical representative of the scalar, as 32 bytes little endian.
+    /// panics if the scalar field of the curve is larger than 256 bits.
+    pub fn to_bytes_canonical(&self) -> [u8; 32] {
+        assert!(E::Fr::size_in_bits() <= 256, "scalar field larger than 256 bits");
+        let mut out = [0u8; 32];
+        let repr = self.bn.into_repr();
+        for (chunk, limb) in out.chunks_mut(8).zip(repr.as_ref()) {
+            chunk.copy_from_slice(&limb.to_le_bytes());
+        }
+        out
+    }
+
+    /// interpret `bytes` as a little endian integer of any length and reduce it
+    /// modulo the group order; every input gives a scalar
+    pub fn from_bytes_mod_order(bytes: &[u8]) -> Scalar<E> {
         Scalar {
-            bn: BigInt381::read(bytes).expect("Could not create PublicKey from bytes"),
+            bn: E::Fr::from_le_bytes_mod_order(bytes),
+        }
+    }
+
+    /// decode the output of `to_bytes_canonical`, rejecting any value that is
+    /// not below the group order
+    pub fn from_bytes_canonical(bytes: &[u8; 32]) -> Result<Scalar<E>, PvssError> {
+        let mut repr = <E::Fr as PrimeField>::BigInt::default();
+        let limbs = repr.as_mut();
+        for (i, chunk) in bytes.chunks(8).enumerate() {
+            let mut limb = [0u8; 8];
+            limb.copy_from_slice(chunk);
+            let limb = u64::from_le_bytes(limb);
+            match limbs.get_mut(i) {
+                Some(l) => *l = limb,
+                None if limb == 0 => {}
+                None => return Err(PvssError::InvalidScalarEncoding),
+            }
+        }
+        E::Fr::from_repr(repr)
+            .map(|bn| Scalar { bn })
+            .ok_or(PvssError::InvalidScalarEncoding)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Scalar<E>, DecodeError> {
+        if bytes.len() != scalar_bytes::<E>() {
+            return Err(DecodeError::InvalidLength);
         }
+        let bn = E::Fr::read(bytes).map_err(|_| DecodeError::InvalidScalar)?;
+        Ok(Scalar { bn })
+    }
+
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Scalar<E>, PvssError> {
+        Self::from_bytes(bytes).map_err(scalar_error)
+    }
+
+    /// hex of `to_bytes`
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.to_bytes())
+    }
+
+    pub fn from_hex(s: &str) -> Result<Scalar<E>, DecodeError> {
+        Self::from_bytes(&decode_hex(s)?)
     }
 }
 
-impl Clone for Scalar {
-    fn clone(&self) -> Scalar {
+impl<E: Curve> Clone for Scalar<E> {
+    fn clone(&self) -> Scalar<E> {
         Scalar {
-            bn: self.bn.clone(),
+            bn: self.bn,
         }
     }
 }
 
-impl Add for Scalar {
+impl<E: Curve> Add for Scalar<E> {
     type Output = Self;
     fn add(self, s: Self) -> Self {
         Scalar { bn: self.bn + s.bn }
     }
 }
 
-impl Sub for Scalar {
+impl<E: Curve> Sub for Scalar<E> {
     type Output = Self;
     fn sub(self, s: Self) -> Self {
         Scalar { bn: self.bn - s.bn }
     }
 }
 
-impl Mul for Scalar {
+impl<E: Curve> Mul for Scalar<E> {
     type Output = Self;
     fn mul(self, s: Self) -> Self {
         Scalar { bn: self.bn * s.bn }
     }
 }
 
-impl PartialEq for Scalar {
-    fn eq(&self, other: &Self) -> bool {
-        self.bn == other.bn
+impl<E: Curve> AddAssign for Scalar<E> {
+    fn add_assign(&mut self, s: Self) {
+        self.bn += s.bn;
     }
 }
 
-impl Point {
-    pub fn infinity() -> Point {
-        Point {
-            point: get_point_at_infinity(),
-        }
+impl<E: Curve> SubAssign for Scalar<E> {
+    fn sub_assign(&mut self, s: Self) {
+        self.bn -= s.bn;
     }
+}
 
-    pub fn generator() -> Point {
-        Point {
-            point: curve_generator(),
-        }
+impl<E: Curve> MulAssign for Scalar<E> {
+    fn mul_assign(&mut self, s: Self) {
+        self.bn *= s.bn;
     }
+}
 
-    pub fn from_scalar(s: &Scalar) -> Point {
-        let gen = curve_generator();
-        let p = gen.into_affine().mul(s.bn);
-        Point { point: p }
+// reference versions of the operators, to avoid cloning in loops
+impl<E: Curve> Add<&Scalar<E>> for &Scalar<E> {
+    type Output = Scalar<E>;
+    fn add(self, s: &Scalar<E>) -> Scalar<E> {
+        Scalar { bn: self.bn + s.bn }
     }
+}
 
-    pub fn mul(&self, s: &Scalar) -> Point {
-        Point {
-            point: self.point.into_affine().mul(s.bn),
-        }
+impl<E: Curve> Sub<&Scalar<E>> for &Scalar<E> {
+    type Output = Scalar<E>;
+    fn sub(self, s: &Scalar<E>) -> Scalar<E> {
+        Scalar { bn: self.bn - s.bn }
     }
+}
 
-    pub fn inverse(&self) -> Point {
-        Point { point: -self.point }
+impl<E: Curve> Mul<&Scalar<E>> for &Scalar<E> {
+    type Output = Scalar<E>;
+    fn mul(self, s: &Scalar<E>) -> Scalar<E> {
+        Scalar { bn: self.bn * s.bn }
     }
+}
 
-    pub fn to_bytes(&self) -> Vec<u8> {
-        let mut buf: Vec<u8> = Vec::new();
-        self.point.into_affine().write(&mut buf).expect("");
-        buf
+impl<E: Curve> Add<&Scalar<E>> for Scalar<E> {
+    type Output = Scalar<E>;
+    fn add(self, s: &Scalar<E>) -> Scalar<E> {
+        Scalar { bn: self.bn + s.bn }
     }
 }
 
-impl Clone for Point {
-    fn clone(&self) -> Point {
-        Point {
-            point: self.point.clone(),
-        }
+impl<E: Curve> Sub<&Scalar<E>> for Scalar<E> {
+    type Output = Scalar<E>;
+    fn sub(self, s: &Scalar<E>) -> Scalar<E> {
+        Scalar { bn: self.bn - s.bn }
+    }
+}
+
+impl<E: Curve> Mul<&Scalar<E>> for Scalar<E> {
+    type Output = Scalar<E>;
+    fn mul(self, s: &Scalar<E>) -> Scalar<E> {
+        Scalar { bn: self.bn * s.bn }
+    }
+}
+
+impl<E: Curve> Sum for Scalar<E> {
+    fn sum<I: Iterator<Item = Scalar<E>>>(iter: I) -> Scalar<E> {
+        iter.fold(Scalar::zero(), |acc, s| acc + s)
+    }
+}
+
+impl<'a, E: Curve> Sum<&'a Scalar<E>> for Scalar<E> {
+    fn sum<I: Iterator<Item = &'a Scalar<E>>>(iter: I) -> Scalar<E> {
+        iter.fold(Scalar::zero(), |acc, s| acc + s)
+    }
+}
+
+impl<E: Curve> Product for Scalar<E> {
+    fn product<I: Iterator<Item = Scalar<E>>>(iter: I) -> Scalar<E> {
+        iter.fold(Scalar::multiplicative_identity(), |acc, s| acc * s)
+    }
+}
+
+impl<'a, E: Curve> Product<&'a Scalar<E>> for Scalar<E> {
+    fn product<I: Iterator<Item = &'a Scalar<E>>>(iter: I) -> Scalar<E> {
+        iter.fold(Scalar::multiplicative_identity(), |acc, s| acc * s)
     }
 }
 
-impl Add for Point {
+// panics when dividing by zero, like integer division; see `checked_div`
+impl<E: Curve> Div for Scalar<E> {
     type Output = Self;
-    fn add(self, p: Self) -> Self {
-        Point {
-            point:self.point + p.point, 
-        }
+    fn div(self, s: Self) -> Self {
+        self.checked_div(&s).expect("division by zero scalar")
+    }
+}
+
+impl<E: Curve> Div<&Scalar<E>> for &Scalar<E> {
+    type Output = Scalar<E>;
+    fn div(self, s: &Scalar<E>) -> Scalar<E> {
+        self.checked_div(s).expect("division by zero scalar")
+    }
+}
+
+impl<E: Curve> DivAssign for Scalar<E> {
+    fn div_assign(&mut self, s: Self) {
+        *self = &*self / &s;
     }
 }
 
-impl Sub for Point {
+impl<E: Curve> Neg for Scalar<E> {
     type Output = Self;
-    fn sub(self, p: Self) -> Self {
-        Point {
-            point:self.point - p.point, 
+    fn neg(self) -> Self {
+        Scalar { bn: -self.bn }
+    }
+}
+
+impl<E: Curve> Default for Scalar<E> {
+    fn default() -> Scalar<E> {
+        Scalar::zero()
+    }
+}
+
+// `==` on scalars is not constant time; use `ct_eq` to compare secrets.
+// `PrivateKey`'s `==` goes through `ct_eq`; the other comparisons in the
+// library are of public values (challenges, points).
+impl<E: Curve> PartialEq for Scalar<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bn == other.bn
+    }
+}
+
+impl<E: Curve> ConstantTimeEq for Scalar<E> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.bn.into_repr().as_ref().ct_eq(other.bn.into_repr().as_ref())
+    }
+}
+
+impl<E: Curve> Scalar<E> {
+    /// `a` if `choice` is 0 and `b` if it is 1, without branching on `choice`
+    pub fn conditional_select(a: &Scalar<E>, b: &Scalar<E>, choice: Choice) -> Scalar<E> {
+        let mut repr = a.bn.into_repr();
+        let other = b.bn.into_repr();
+        for (x, y) in repr.as_mut().iter_mut().zip(other.as_ref()) {
+            *x = u64::conditional_select(x, y, choice);
+        }
+        Scalar {
+            bn: E::Fr::from_repr(repr).expect("both inputs are reduced"),
         }
     }
 }
 
-impl PartialEq for Point {
+impl<E: Curve> ConstantTimeEq for PrivateKey<E> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.scalar.ct_eq(&other.scalar)
+    }
+}
+
+// private keys are always compared in constant time
+impl<E: Curve> PartialEq for PrivateKey<E> {
     fn eq(&self, other: &Self) -> bool {
-        self.point.into_affine() == other.point.into_affine()
+        self.ct_eq(other).into()
+    }
+}
+
+// scalars are mostly secrets (keys, shares, nonces), so never print them
+impl<E: Curve> fmt::Debug for Scalar<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Scalar(<redacted>)")
+    }
+}
+
+impl<E: Curve> fmt::Debug for PrivateKey<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PrivateKey(<redacted>)")
+    }
+}
+
+// every scalar is wiped when dropped, which covers the private keys and the
+// intermediate values of the share decryption
+#[cfg(feature = "zeroize")]
+impl<E: Curve> Zeroize for Scalar<E> {
+    fn zeroize(&mut self) {
+        self.bn.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<E: Curve> Drop for Scalar<E> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<E: Curve> ZeroizeOnDrop for Scalar<E> {}
+
+#[cfg(feature = "zeroize")]
+impl<E: Curve> Zeroize for PrivateKey<E> {
+    fn zeroize(&mut self) {
+        self.scalar.zeroize();
+    }
+}
+
+// the inner scalar wipes itself on drop
+#[cfg(feature = "zeroize")]
+impl<E: Curve> ZeroizeOnDrop for PrivateKey<E> {}
+
+impl<E: Curve> Point<E> {
+    pub fn infinity() -> Point<E> {
+        Point {
+            point: get_point_at_infinity::<E>(),
+        }
+    }
+
+    /// whether this is the point at infinity, `Point::infinity()`
+    pub fn is_identity(&self) -> bool {
+        self.point.is_zero()
+    }
+
+    /// the standard generator of the curve (for BLS12-381, the G1 generator
+    /// of the specification), base of every public key. this is fixed so
+    /// that serialized keys and proofs stay valid; changing it would be a
+    /// breaking change.
+    pub fn generator() -> Point<E> {
+        Point {
+            point: E::cached_generator().into_projective(),
+        }
+    }
+
+    /// a second generator, whose discrete log relative to `Point::generator`
+    /// is unknown, as needed by Pedersen style commitments
+    pub fn generator_h() -> Point<E> {
+        Point {
+            point: E::cached_generator_h().into_projective(),
+        }
+    }
+
+    /// hash `msg` to a point of the prime order subgroup, with the domain
+    /// separation tag `dst`. the result is deterministic, and its discrete
+    /// log relative to any other point is unknown.
+    ///
+    /// on BLS12-381 this is the RFC 9380 suite
+    /// `BLS12381G1_XMD:SHA-256_SSWU_RO_`, and matches other implementations
+    /// given the same `dst`. on BN254 it is a non-standard try-and-increment
+    /// map instead.
+    pub fn hash_to_curve(msg: &[u8], dst: &[u8]) -> Point<E> {
+        Point {
+            point: E::hash_to_g1(msg, dst),
+        }
+    }
+
+    /// an independent generator derived from `label`, "nothing up my sleeve"
+    pub fn nums_generator(label: &str) -> Point<E> {
+        Self::hash_to_curve(label.as_bytes(), E::NUMS_DST)
+    }
+
+    pub fn from_scalar(s: &Scalar<E>) -> Point<E> {
+        E::cached_generator_table().mul(s)
+    }
+
+    /// `from_scalar`, rejecting the zero scalar whose point is the identity
+    pub fn from_scalar_checked(s: &Scalar<E>) -> Result<Point<E>, KeyError> {
+        if s.is_zero() {
+            return Err(KeyError::ZeroScalar);
+        }
+        Ok(Self::from_scalar(s))
+    }
+
+    /// `generator() * s` through the fixed-base table, the same as
+    /// `from_scalar`; much faster than `mul` when many scalars share the base
+    pub fn mul_base(s: &Scalar<E>) -> Point<E> {
+        Self::from_scalar(s)
+    }
+
+    /// `self + self`
+    pub fn double(&self) -> Point<E> {
+        Point {
+            point: self.point.double(),
+        }
+    }
+
+    pub fn mul(&self, s: &Scalar<E>) -> Point<E> {
+        Point {
+            point: self.point.into_affine().mul(s.bn.into_repr()),
+        }
+    }
+
+    pub fn inverse(&self) -> Point<E> {
+        Point { point: -self.point }
+    }
+
+    #[doc(hidden)]
+    #[deprecated(note = "use `is_identity`")]
+    pub fn is_infinity(&self) -> bool {
+        self.is_identity()
+    }
+
+    /// compute `points[0] * scalars[0] + .. + points[n] * scalars[n]` in a
+    /// single multi scalar multiplication, which is much faster than summing
+    /// each product. the sum of no terms is the point at infinity.
+    pub fn multi_scalar_mul(points: &[Point<E>], scalars: &[Scalar<E>]) -> Point<E> {
+        assert_eq!(
+            points.len(),
+            scalars.len(),
+            "multi_scalar_mul: different number of points and scalars"
+        );
+        let projective: Vec<E::G1Projective> = points.iter().map(|p| p.point).collect();
+        let bases = E::G1Projective::batch_normalization_into_affine(&projective);
+        let scalars: Vec<_> = scalars.iter().map(|s| s.bn.into_repr()).collect();
+        Point {
+            point: VariableBaseMSM::multi_scalar_mul(&bases, &scalars),
+        }
+    }
+
+    /// check the curve equation only, which is cheaper than the subgroup check
+    pub fn is_on_curve(&self) -> bool {
+        self.point.into_affine().is_on_curve()
+    }
+
+    /// check that the point is in the prime order subgroup, which holds for
+    /// every point computed by this library but not necessarily for decoded ones
+    pub fn is_in_correct_subgroup(&self) -> bool {
+        self.point
+            .into_affine()
+            .is_in_correct_subgroup_assuming_on_curve()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf: Vec<u8> = Vec::with_capacity(point_bytes::<E>());
+        self.write_to(&mut buf).expect("");
+        buf
+    }
+
+    /// write `to_bytes` directly to `w`
+    pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        self.point.into_affine().write(w)
+    }
+
+    /// read a point written by `write_to`, with the same checks as `from_bytes`
+    pub fn read_from<R: Read>(r: &mut R) -> Result<Point<E>, PvssError> {
+        let affine = GroupAffine::<E::G1Parameters>::read(r)
+            .map_err(|_| PvssError::InvalidPointEncoding)?;
+        let affine = check_affine(affine).map_err(point_error)?;
+        Ok(Point {
+            point: affine.into_projective(),
+        })
+    }
+
+    /// `to_bytes` of every point, normalizing them to affine coordinates
+    /// together so that the whole batch costs a single field inversion
+    pub fn batch_to_bytes(points: &[Point<E>]) -> Vec<Vec<u8>> {
+        let projective: Vec<E::G1Projective> = points.iter().map(|p| p.point).collect();
+        E::G1Projective::batch_normalization_into_affine(&projective)
+            .iter()
+            .map(|affine| {
+                let mut buf: Vec<u8> = Vec::new();
+                affine.write(&mut buf).expect("");
+                buf
+            })
+            .collect()
+    }
+
+    /// `to_bytes_compressed` of every point, with a single field inversion
+    pub fn batch_to_bytes_compressed(points: &[Point<E>]) -> Vec<Vec<u8>> {
+        let projective: Vec<E::G1Projective> = points.iter().map(|p| p.point).collect();
+        E::G1Projective::batch_normalization_into_affine(&projective)
+            .iter()
+            .map(|affine| {
+                let mut buf: Vec<u8> = Vec::new();
+                affine.serialize(&mut buf).expect("");
+                buf
+            })
+            .collect()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Point<E>, DecodeError> {
+        let affine = read_affine::<E::G1Parameters>(bytes, point_bytes::<E>())?;
+        Ok(Point {
+            point: affine.into_projective(),
+        })
+    }
+
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Point<E>, PvssError> {
+        Self::from_bytes(bytes).map_err(point_error)
+    }
+
+    /// hex of `to_bytes`
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.to_bytes())
+    }
+
+    pub fn from_hex(s: &str) -> Result<Point<E>, DecodeError> {
+        Self::from_bytes(&decode_hex(s)?)
+    }
+
+    /// uncompressed encoding, the same as `to_bytes`: both coordinates and the
+    /// infinity flag (97 bytes on BLS12-381). twice the size of the compressed
+    /// encoding, but decoding needs no square root
+    pub fn to_bytes_uncompressed(&self) -> Vec<u8> {
+        self.to_bytes()
+    }
+
+    /// check that the point is on the curve and in the prime order subgroup
+    pub fn from_bytes_uncompressed(bytes: &[u8]) -> Result<Point<E>, PvssError> {
+        Self::try_from_bytes(bytes)
+    }
+
+    /// compressed encoding: the x coordinate, with the sign of y and the
+    /// infinity flag in the top bits (48 bytes on BLS12-381)
+    pub fn to_bytes_compressed(&self) -> Vec<u8> {
+        let mut buf: Vec<u8> = Vec::new();
+        self.point.into_affine().serialize(&mut buf).expect("");
+        buf
+    }
+
+    /// decompress and check that the point is in the prime order subgroup
+    pub fn from_bytes_compressed(bytes: &[u8]) -> Result<Point<E>, PvssError> {
+        Self::from_compressed(bytes).map_err(point_error)
+    }
+
+    pub(crate) fn from_compressed(bytes: &[u8]) -> Result<Point<E>, DecodeError> {
+        let affine = read_compressed::<E::G1Parameters>(bytes)?;
+        Ok(Point {
+            point: affine.into_projective(),
+        })
+    }
+}
+
+// decompress a point written by `CanonicalSerialize`, checking the subgroup
+fn read_compressed<P: SWModelParameters>(bytes: &[u8]) -> Result<GroupAffine<P>, DecodeError> {
+    if bytes.len() != P::BaseField::zero().serialized_size_with_flags::<SWFlags>() {
+        return Err(DecodeError::InvalidLength);
+    }
+    let (x, flags): (P::BaseField, SWFlags) =
+        CanonicalDeserializeWithFlags::deserialize_with_flags(bytes)
+            .map_err(|_| DecodeError::InvalidPoint)?;
+    let affine = match flags.is_positive() {
+        // only accept the canonical encoding of the point at infinity
+        None if x.is_zero() => GroupAffine::<P>::zero(),
+        None => return Err(DecodeError::InvalidPoint),
+        Some(greatest) => {
+            GroupAffine::<P>::get_point_from_x(x, greatest).ok_or(DecodeError::NotOnCurve)?
+        }
+    };
+    if !affine.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(DecodeError::NotInSubgroup);
+    }
+    Ok(affine)
+}
+
+impl<E: Curve> Clone for Point<E> {
+    fn clone(&self) -> Point<E> {
+        Point {
+            point: self.point,
+        }
+    }
+}
+
+impl<E: Curve> Add for Point<E> {
+    type Output = Self;
+    fn add(self, p: Self) -> Self {
+        Point {
+            point:self.point + p.point, 
+        }
+    }
+}
+
+impl<E: Curve> Sub for Point<E> {
+    type Output = Self;
+    fn sub(self, p: Self) -> Self {
+        Point {
+            point:self.point - p.point, 
+        }
+    }
+}
+
+impl<E: Curve> AddAssign for Point<E> {
+    fn add_assign(&mut self, p: Self) {
+        self.point += p.point;
+    }
+}
+
+impl<E: Curve> SubAssign for Point<E> {
+    fn sub_assign(&mut self, p: Self) {
+        self.point -= p.point;
+    }
+}
+
+impl<E: Curve> Add<&Point<E>> for &Point<E> {
+    type Output = Point<E>;
+    fn add(self, s: &Point<E>) -> Point<E> {
+        Point {
+            point: self.point + s.point,
+        }
+    }
+}
+
+impl<E: Curve> Sub<&Point<E>> for &Point<E> {
+    type Output = Point<E>;
+    fn sub(self, s: &Point<E>) -> Point<E> {
+        Point {
+            point: self.point - s.point,
+        }
+    }
+}
+
+impl<E: Curve> Add<&Point<E>> for Point<E> {
+    type Output = Point<E>;
+    fn add(self, p: &Point<E>) -> Point<E> {
+        Point {
+            point: self.point + p.point,
+        }
+    }
+}
+
+impl<E: Curve> Sub<&Point<E>> for Point<E> {
+    type Output = Point<E>;
+    fn sub(self, p: &Point<E>) -> Point<E> {
+        Point {
+            point: self.point - p.point,
+        }
+    }
+}
+
+impl<E: Curve> Sum for Point<E> {
+    fn sum<I: Iterator<Item = Point<E>>>(iter: I) -> Point<E> {
+        iter.fold(Point::infinity(), |acc, p| acc + p)
+    }
+}
+
+impl<'a, E: Curve> Sum<&'a Point<E>> for Point<E> {
+    fn sum<I: Iterator<Item = &'a Point<E>>>(iter: I) -> Point<E> {
+        iter.fold(Point::infinity(), |acc, p| acc + p)
+    }
+}
+
+// `&point * &scalar`. there is no `Mul` on owned points, which would shadow
+// the inherent `Point::mul` in method calls.
+impl<E: Curve> Mul<&Scalar<E>> for &Point<E> {
+    type Output = Point<E>;
+    fn mul(self, s: &Scalar<E>) -> Point<E> {
+        Point::mul(self, s)
+    }
+}
+
+impl<E: Curve> Mul<Scalar<E>> for &Point<E> {
+    type Output = Point<E>;
+    fn mul(self, s: Scalar<E>) -> Point<E> {
+        Point::mul(self, &s)
+    }
+}
+
+impl<E: Curve> Neg for Point<E> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        self.inverse()
+    }
+}
+
+impl<E: Curve> Default for Point<E> {
+    fn default() -> Point<E> {
+        Point::infinity()
+    }
+}
+
+// projective equality cross multiplies the coordinates, which is cheaper than
+// normalizing both sides to affine coordinates
+impl<E: Curve> PartialEq for Point<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.point == other.point
+    }
+}
+
+impl<E: Curve> Eq for Point<E> {}
+
+// hash the compressed encoding so that equal points hash the same whatever
+// their projective coordinates
+impl<E: Curve> Hash for Point<E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_bytes_compressed().hash(state)
+    }
+}
+
+// read an affine point written by `ToBytes`, checking what the reader does
+// not: the curve has a large cofactor, and points off the subgroup would allow
+// small subgroup attacks on the shares
+fn read_affine<P: SWModelParameters>(
+    bytes: &[u8],
+    len: usize,
+) -> Result<GroupAffine<P>, DecodeError> {
+    if bytes.len() != len {
+        return Err(DecodeError::InvalidLength);
+    }
+    let affine = GroupAffine::<P>::read(bytes).map_err(|_| DecodeError::InvalidPoint)?;
+    check_affine(affine)
+}
+
+fn check_affine<P: SWModelParameters>(
+    affine: GroupAffine<P>,
+) -> Result<GroupAffine<P>, DecodeError> {
+    // only accept the canonical encoding of the point at infinity
+    if affine.infinity && affine != GroupAffine::<P>::zero() {
+        return Err(DecodeError::InvalidPoint);
+    }
+    if !affine.is_on_curve() {
+        return Err(DecodeError::NotOnCurve);
+    }
+    if !affine.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(DecodeError::NotInSubgroup);
+    }
+    Ok(affine)
+}
+
+// the arkworks serialization, so that the types can be part of larger
+// arkworks structures. points are checked like `from_bytes` and
+// `from_bytes_compressed` on the way in.
+impl<E: Curve> CanonicalSerialize for Scalar<E> {
+    fn serialize<W: Write>(&self, writer: W) -> Result<(), SerializationError> {
+        self.bn.serialize(writer)
+    }
+
+    fn serialized_size(&self) -> usize {
+        self.bn.serialized_size()
+    }
+}
+
+impl<E: Curve> CanonicalDeserialize for Scalar<E> {
+    fn deserialize<R: Read>(reader: R) -> Result<Self, SerializationError> {
+        Ok(Scalar {
+            bn: E::Fr::deserialize(reader)?,
+        })
+    }
+}
+
+impl<E: Curve> CanonicalSerialize for Point<E> {
+    fn serialize<W: Write>(&self, writer: W) -> Result<(), SerializationError> {
+        self.point.into_affine().serialize(writer)
+    }
+
+    fn serialized_size(&self) -> usize {
+        self.point.into_affine().serialized_size()
+    }
+
+    fn serialize_uncompressed<W: Write>(&self, writer: W) -> Result<(), SerializationError> {
+        self.point.into_affine().serialize_uncompressed(writer)
+    }
+
+    fn uncompressed_size(&self) -> usize {
+        self.point.into_affine().uncompressed_size()
+    }
+}
+
+impl<E: Curve> CanonicalDeserialize for Point<E> {
+    fn deserialize<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let mut bytes = vec![0u8; E::Fq::zero().serialized_size_with_flags::<SWFlags>()];
+        reader.read_exact(&mut bytes)?;
+        Point::from_compressed(&bytes).map_err(|_| SerializationError::InvalidData)
+    }
+
+    fn deserialize_uncompressed<R: Read>(reader: R) -> Result<Self, SerializationError> {
+        let affine = E::G1Affine::deserialize_unchecked(reader)?;
+        let affine = check_affine(affine).map_err(|_| SerializationError::InvalidData)?;
+        Ok(Point {
+            point: affine.into_projective(),
+        })
+    }
+}
+
+impl<E: Curve> CanonicalSerialize for PublicKey<E> {
+    fn serialize<W: Write>(&self, writer: W) -> Result<(), SerializationError> {
+        self.point.serialize(writer)
+    }
+
+    fn serialized_size(&self) -> usize {
+        self.point.serialized_size()
+    }
+
+    fn serialize_uncompressed<W: Write>(&self, writer: W) -> Result<(), SerializationError> {
+        self.point.serialize_uncompressed(writer)
+    }
+
+    fn uncompressed_size(&self) -> usize {
+        self.point.uncompressed_size()
+    }
+}
+
+impl<E: Curve> CanonicalDeserialize for PublicKey<E> {
+    fn deserialize<R: Read>(reader: R) -> Result<Self, SerializationError> {
+        Ok(PublicKey {
+            point: Point::deserialize(reader)?,
+        })
+    }
+
+    fn deserialize_uncompressed<R: Read>(reader: R) -> Result<Self, SerializationError> {
+        Ok(PublicKey {
+            point: Point::deserialize_uncompressed(reader)?,
+        })
+    }
+}
+
+impl<E: Curve> CanonicalSerialize for PrivateKey<E> {
+    fn serialize<W: Write>(&self, writer: W) -> Result<(), SerializationError> {
+        self.scalar.serialize(writer)
+    }
+
+    fn serialized_size(&self) -> usize {
+        self.scalar.serialized_size()
+    }
+}
+
+impl<E: Curve> CanonicalDeserialize for PrivateKey<E> {
+    fn deserialize<R: Read>(reader: R) -> Result<Self, SerializationError> {
+        Ok(PrivateKey {
+            scalar: Scalar::deserialize(reader)?,
+        })
+    }
+}
+
+// vectors are encoded as a u32 count followed by every element prefixed by
+// its u32 length, all little endian
+fn encode_vec(elements: &[Vec<u8>]) -> Vec<u8> {
+    let size = 4 + elements.iter().map(|e| 4 + e.len()).sum::<usize>();
+    let mut buf = Vec::with_capacity(size);
+    buf.extend_from_slice(&(elements.len() as u32).to_le_bytes());
+    for e in elements {
+        buf.extend_from_slice(&(e.len() as u32).to_le_bytes());
+        buf.extend_from_slice(e);
+    }
+    buf
+}
+
+fn read_u32(bytes: &mut &[u8]) -> Option<usize> {
+    if bytes.len() < 4 {
+        return None;
+    }
+    let (n, rest) = bytes.split_at(4);
+    *bytes = rest;
+    Some(u32::from_le_bytes([n[0], n[1], n[2], n[3]]) as usize)
+}
+
+fn decode_vec<T, F>(mut bytes: &[u8], err: PvssError, decode: F) -> Result<Vec<T>, PvssError>
+where
+    F: Fn(&[u8]) -> Result<T, PvssError>,
+{
+    let count = read_u32(&mut bytes).ok_or(err)?;
+    // every element takes at least its length prefix, so a larger count
+    // cannot be honest and must not be used to size the allocation
+    if count > bytes.len() / 4 {
+        return Err(err);
+    }
+    let mut out = Vec::with_capacity(count);
+    for _ in 0..count {
+        let len = read_u32(&mut bytes).ok_or(err)?;
+        if len > bytes.len() {
+            return Err(err);
+        }
+        let (element, rest) = bytes.split_at(len);
+        out.push(decode(element)?);
+        bytes = rest;
+    }
+    if !bytes.is_empty() {
+        return Err(err);
+    }
+    Ok(out)
+}
+
+pub fn encode_points<E: Curve>(points: &[Point<E>]) -> Vec<u8> {
+    encode_vec(&Point::batch_to_bytes(points))
+}
+
+/// decode the output of `encode_points`; every element may be in either the
+/// uncompressed or the compressed encoding
+pub fn decode_points<E: Curve>(bytes: &[u8]) -> Result<Vec<Point<E>>, PvssError> {
+    decode_vec(bytes, PvssError::InvalidPointEncoding, |b| {
+        if b.len() == point_bytes::<E>() {
+            Point::try_from_bytes(b)
+        } else {
+            Point::from_bytes_compressed(b)
+        }
+    })
+}
+
+// bytes that fit below the group order: the scalar is a little endian
+// integer of that many bytes, a length byte followed by the secret
+fn secret_capacity<E: Curve>() -> usize {
+    (E::Fr::size_in_bits() - 1) / 8
+}
+
+/// pack a short secret, such as a symmetric key, into a scalar that can be
+/// shared: up to 30 bytes on BLS12-381
+pub fn encode_secret<E: Curve>(bytes: &[u8]) -> Result<Scalar<E>, PvssError> {
+    if 1 + bytes.len() > secret_capacity::<E>() {
+        return Err(PvssError::SecretTooLong);
+    }
+    let mut out = [0u8; 32];
+    out[0] = bytes.len() as u8;
+    out[1..1 + bytes.len()].copy_from_slice(bytes);
+    Scalar::from_bytes_canonical(&out)
+}
+
+/// the secret packed by `encode_secret`; other scalars are rejected
+pub fn decode_secret<E: Curve>(s: &Scalar<E>) -> Result<Vec<u8>, PvssError> {
+    let bytes = s.to_bytes_canonical();
+    let end = 1 + bytes[0] as usize;
+    if end > secret_capacity::<E>() || bytes[end..].iter().any(|b| *b != 0) {
+        return Err(PvssError::InvalidScalarEncoding);
+    }
+    Ok(bytes[1..end].to_vec())
+}
+
+pub fn encode_scalars<E: Curve>(scalars: &[Scalar<E>]) -> Vec<u8> {
+    let elements: Vec<Vec<u8>> = scalars.iter().map(|s| s.to_bytes()).collect();
+    encode_vec(&elements)
+}
+
+pub fn decode_scalars<E: Curve>(bytes: &[u8]) -> Result<Vec<Scalar<E>>, PvssError> {
+    decode_vec(bytes, PvssError::InvalidScalarEncoding, Scalar::try_from_bytes)
+}
+
+/// bits of the scalar handled by each addition of `GeneratorTable::mul`, for
+/// `GeneratorTable::new`
+pub const TABLE_WINDOW: usize = 8;
+
+/// precomputed multiples of a fixed base, so that multiplying the base costs
+/// one addition per window of the scalar instead of a full double and add.
+/// the table of `Point::generator` is built on first use and shared.
+///
+/// a table with a window of `w` bits holds `ceil(255 / w) * 2^w` affine
+/// points on BLS12-381, of 104 bytes each: about 850 KB for the default
+/// window of 8, and 100 KB for a window of 4.
+pub struct GeneratorTable<E: Curve> {
+    window: usize,
+    outerc: usize,
+    table: Vec<Vec<E::G1Affine>>,
+}
+
+impl<E: Curve> GeneratorTable<E> {
+    pub fn new(base: &Point<E>) -> GeneratorTable<E> {
+        Self::with_window(base, TABLE_WINDOW)
+    }
+
+    /// a table with a window of `window` bits, trading memory for speed.
+    /// panics if `window` is 0.
+    pub fn with_window(base: &Point<E>, window: usize) -> GeneratorTable<E> {
+        assert!(window > 0, "the window of a table is at least one bit");
+        let bits = E::Fr::size_in_bits();
+        GeneratorTable {
+            window,
+            outerc: bits.div_ceil(window),
+            table: FixedBaseMSM::get_window_table(bits, window, base.point),
+        }
+    }
+
+    /// the table of `Point::generator`
+    pub fn generator() -> &'static GeneratorTable<E> {
+        E::cached_generator_table()
+    }
+
+    pub fn mul(&self, s: &Scalar<E>) -> Point<E> {
+        Point {
+            point: FixedBaseMSM::windowed_mul(self.outerc, self.window, &self.table, &s.bn),
+        }
+    }
+}
+
+/// a point of the G2 group of the curve, for pairing equations
+pub struct PointG2<E: Curve> {
+    point: E::G2Projective,
+}
+
+impl<E: Curve> PointG2<E> {
+    pub fn infinity() -> PointG2<E> {
+        PointG2 {
+            point: E::G2Projective::zero(),
+        }
+    }
+
+    /// the standard generator of G2
+    pub fn generator() -> PointG2<E> {
+        PointG2 {
+            point: E::G2Projective::prime_subgroup_generator(),
+        }
+    }
+
+    pub fn from_scalar(s: &Scalar<E>) -> PointG2<E> {
+        Self::generator().mul(s)
+    }
+
+    /// hash `msg` to a point of the prime order subgroup, with the domain
+    /// separation tag `dst`. unlike `Point::hash_to_curve`, this is a
+    /// non-standard try-and-increment map, not the RFC 9380 SSWU suite for G2:
+    /// its outputs do not interoperate with other libraries, and it is not
+    /// constant time in `msg`.
+    pub fn hash_to_curve_try_and_increment(msg: &[u8], dst: &[u8]) -> PointG2<E> {
+        PointG2 {
+            point: try_and_increment_g2::<E>(msg, dst),
+        }
+    }
+
+    pub fn mul(&self, s: &Scalar<E>) -> PointG2<E> {
+        PointG2 {
+            point: self.point.into_affine().mul(s.bn.into_repr()),
+        }
+    }
+
+    pub fn inverse(&self) -> PointG2<E> {
+        PointG2 { point: -self.point }
+    }
+
+    /// whether this is the point at infinity, `PointG2::infinity()`
+    pub fn is_identity(&self) -> bool {
+        self.point.is_zero()
+    }
+
+    #[doc(hidden)]
+    #[deprecated(note = "use `is_identity`")]
+    pub fn is_infinity(&self) -> bool {
+        self.is_identity()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf: Vec<u8> = Vec::new();
+        self.point.into_affine().write(&mut buf).expect("");
+        buf
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<PointG2<E>, DecodeError> {
+        let affine = read_affine::<E::G2Parameters>(bytes, point_g2_bytes::<E>())?;
+        Ok(PointG2 {
+            point: affine.into_projective(),
+        })
+    }
+
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<PointG2<E>, PvssError> {
+        Self::from_bytes(bytes).map_err(point_error)
+    }
+
+    /// hex of `to_bytes`
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.to_bytes())
+    }
+
+    pub fn from_hex(s: &str) -> Result<PointG2<E>, DecodeError> {
+        Self::from_bytes(&decode_hex(s)?)
+    }
+
+    /// the x coordinate with the flags in the top bits, as for `Point` (96
+    /// bytes on BLS12-381)
+    pub fn to_bytes_compressed(&self) -> Vec<u8> {
+        let mut buf: Vec<u8> = Vec::new();
+        self.point.into_affine().serialize(&mut buf).expect("");
+        buf
+    }
+
+    /// decompress and check that the point is in the prime order subgroup
+    pub fn from_bytes_compressed(bytes: &[u8]) -> Result<PointG2<E>, PvssError> {
+        Self::from_compressed(bytes).map_err(point_error)
+    }
+
+    pub(crate) fn from_compressed(bytes: &[u8]) -> Result<PointG2<E>, DecodeError> {
+        let affine = read_compressed::<E::G2Parameters>(bytes)?;
+        Ok(PointG2 {
+            point: affine.into_projective(),
+        })
+    }
+}
+
+impl<E: Curve> CanonicalSerialize for PointG2<E> {
+    fn serialize<W: Write>(&self, writer: W) -> Result<(), SerializationError> {
+        self.point.into_affine().serialize(writer)
+    }
+
+    fn serialized_size(&self) -> usize {
+        self.point.into_affine().serialized_size()
+    }
+
+    fn serialize_uncompressed<W: Write>(&self, writer: W) -> Result<(), SerializationError> {
+        self.point.into_affine().serialize_uncompressed(writer)
+    }
+
+    fn uncompressed_size(&self) -> usize {
+        self.point.into_affine().uncompressed_size()
+    }
+}
+
+impl<E: Curve> CanonicalDeserialize for PointG2<E> {
+    fn deserialize<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let mut bytes = vec![0u8; E::Fqe::zero().serialized_size_with_flags::<SWFlags>()];
+        reader.read_exact(&mut bytes)?;
+        PointG2::from_compressed(&bytes).map_err(|_| SerializationError::InvalidData)
+    }
+
+    fn deserialize_uncompressed<R: Read>(reader: R) -> Result<Self, SerializationError> {
+        let affine = E::G2Affine::deserialize_unchecked(reader)?;
+        let affine = check_affine(affine).map_err(|_| SerializationError::InvalidData)?;
+        Ok(PointG2 {
+            point: affine.into_projective(),
+        })
+    }
+}
+
+impl<E: Curve> Clone for PointG2<E> {
+    fn clone(&self) -> PointG2<E> {
+        PointG2 { point: self.point }
+    }
+}
+
+impl<E: Curve> Add for PointG2<E> {
+    type Output = Self;
+    fn add(self, p: Self) -> Self {
+        PointG2 {
+            point: self.point + p.point,
+        }
+    }
+}
+
+impl<E: Curve> Sub for PointG2<E> {
+    type Output = Self;
+    fn sub(self, p: Self) -> Self {
+        PointG2 {
+            point: self.point - p.point,
+        }
+    }
+}
+
+impl<E: Curve> Neg for PointG2<E> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        self.inverse()
+    }
+}
+
+impl<E: Curve> PartialEq for PointG2<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.point.into_affine() == other.point.into_affine()
+    }
+}
+
+/// an element of the target group of the pairing, written multiplicatively
+pub struct Gt<E: Curve> {
+    value: E::Fqk,
+}
+
+/// the bilinear map `e(p, q)`
+pub fn pairing<E: Curve>(p: &Point<E>, q: &PointG2<E>) -> Gt<E> {
+    Gt {
+        value: E::pairing(p.point, q.point),
+    }
+}
+
+/// check `prod e(lhs) == prod e(rhs)` with a single final exponentiation,
+/// e.g. `e(a, b) * e(c, d) == e(f, g)`
+pub fn pairing_product_eq<E: Curve>(
+    lhs: &[(Point<E>, PointG2<E>)],
+    rhs: &[(Point<E>, PointG2<E>)],
+) -> bool {
+    // move the right side over: prod e(lhs) * prod e(-rhs) == 1
+    let prepared: Vec<(E::G1Prepared, E::G2Prepared)> = lhs
+        .iter()
+        .map(|(p, q)| (p.point, q.point))
+        .chain(rhs.iter().map(|(p, q)| (-p.point, q.point)))
+        .map(|(p, q)| (p.into_affine().into(), q.into_affine().into()))
+        .collect();
+    E::product_of_pairings(&prepared).is_one()
+}
+
+impl<E: Curve> Gt<E> {
+    /// `e(g1, g2)^0`, the neutral element
+    pub fn identity() -> Gt<E> {
+        Gt {
+            value: E::Fqk::one(),
+        }
+    }
+
+    pub fn pow(&self, s: &Scalar<E>) -> Gt<E> {
+        Gt {
+            value: self.value.pow(s.bn.into_repr()),
+        }
+    }
+
+    /// the coefficients of the target field element (576 bytes on BLS12-381)
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf: Vec<u8> = Vec::new();
+        self.value.serialize(&mut buf).expect("");
+        buf
+    }
+
+    /// decode and check that the element is in the order r subgroup of the
+    /// target field, where the pairing takes its values
+    pub fn from_bytes(bytes: &[u8]) -> Result<Gt<E>, DecodeError> {
+        if bytes.len() != E::Fqk::one().serialized_size() {
+            return Err(DecodeError::InvalidLength);
+        }
+        let value = E::Fqk::deserialize(bytes).map_err(|_| DecodeError::InvalidPoint)?;
+        if value.is_zero() || !value.pow(E::Fr::characteristic()).is_one() {
+            return Err(DecodeError::NotInSubgroup);
+        }
+        Ok(Gt { value })
+    }
+}
+
+impl<E: Curve> Clone for Gt<E> {
+    fn clone(&self) -> Gt<E> {
+        Gt { value: self.value }
+    }
+}
+
+impl<E: Curve> Mul for Gt<E> {
+    type Output = Self;
+    fn mul(self, g: Self) -> Self {
+        Gt {
+            value: self.value * g.value,
+        }
+    }
+}
+
+impl<E: Curve> PartialEq for Gt<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+fn fmt_hex(f: &mut fmt::Formatter, bytes: &[u8]) -> fmt::Result {
+    for b in bytes {
+        write!(f, "{:02x}", b)?;
+    }
+    Ok(())
+}
+
+// bytes of the compressed encoding shown by `Debug`, enough to tell points
+// apart in logs and assertion messages
+const DEBUG_PREFIX_BYTES: usize = 8;
+
+// points are public, print a prefix of their compressed encoding
+impl<E: Curve> fmt::Debug for Point<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Point(")?;
+        fmt_hex(f, &self.to_bytes_compressed()[..DEBUG_PREFIX_BYTES])?;
+        write!(f, "..)")
+    }
+}
+
+impl<E: Curve> fmt::Debug for PublicKey<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PublicKey(")?;
+        fmt_hex(f, &self.point.to_bytes_compressed()[..DEBUG_PREFIX_BYTES])?;
+        write!(f, "..)")
+    }
+}
+
+// the full hex of `to_bytes`, as read back by `from_hex`
+impl<E: Curve> fmt::Display for PublicKey<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_hex(f, &self.to_bytes())
+    }
+}
+
+impl<E: Curve> fmt::Debug for PointG2<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut buf: Vec<u8> = Vec::new();
+        self.point.into_affine().serialize(&mut buf).expect("");
+        write!(f, "PointG2(")?;
+        fmt_hex(f, &buf)?;
+        write!(f, ")")
     }
 }
@@ -1,3 +1,84 @@
-mod ark;
+pub mod ark;
+mod hash_to_curve;
+#[cfg(feature = "serde")]
+mod serde_impls;
 
-pub use self::ark::*;
+pub use ark_bls12_381::Bls12_381;
+#[cfg(feature = "bn254")]
+pub use ark_bn254::Bn254;
+use alloc::vec::Vec;
+use rand::{CryptoRng, Rng, RngCore};
+
+use super::error::PvssError;
+
+pub use self::ark::{pairing, Curve, DecodeError, KeyError, TABLE_WINDOW};
+
+// the library works on BLS12-381; the generic types of the `ark` module can
+// be used with any other `Curve`
+pub type Scalar = ark::Scalar<Bls12_381>;
+pub type Point = ark::Point<Bls12_381>;
+pub type PublicKey = ark::PublicKey<Bls12_381>;
+pub type PrivateKey = ark::PrivateKey<Bls12_381>;
+pub type PointG2 = ark::PointG2<Bls12_381>;
+pub type Gt = ark::Gt<Bls12_381>;
+pub type GeneratorTable = ark::GeneratorTable<Bls12_381>;
+
+pub type Bls381Scalar = Scalar;
+pub type Bls381Point = Point;
+pub type Bls381PublicKey = PublicKey;
+pub type Bls381PrivateKey = PrivateKey;
+pub type Bls381PointG2 = PointG2;
+
+#[cfg(feature = "std")]
+pub fn create_keypair() -> (PublicKey, PrivateKey) {
+    ark::create_keypair()
+}
+
+pub fn create_keypair_with<R: RngCore + CryptoRng>(rng: &mut R) -> (PublicKey, PrivateKey) {
+    ark::create_keypair_with(rng)
+}
+
+pub fn create_keypair_with_rng<R: Rng + ?Sized>(rng: &mut R) -> (PublicKey, PrivateKey) {
+    ark::create_keypair_with_rng(rng)
+}
+
+/// the standard generator of G2, `PointG2::generator()`
+pub fn g2_generator() -> PointG2 {
+    PointG2::generator()
+}
+
+pub fn pairing_product_eq(lhs: &[(Point, PointG2)], rhs: &[(Point, PointG2)]) -> bool {
+    ark::pairing_product_eq(lhs, rhs)
+}
+
+pub fn create_keypair_from_seed(seed: &[u8; 32]) -> (PublicKey, PrivateKey) {
+    ark::create_keypair_from_seed(seed)
+}
+
+pub fn create_keypair_from_scalar(s: Scalar) -> Result<(PublicKey, PrivateKey), KeyError> {
+    ark::create_keypair_from_scalar(s)
+}
+
+pub fn encode_points(points: &[Point]) -> Vec<u8> {
+    ark::encode_points(points)
+}
+
+pub fn decode_points(bytes: &[u8]) -> Result<Vec<Point>, PvssError> {
+    ark::decode_points(bytes)
+}
+
+pub fn encode_secret(bytes: &[u8]) -> Result<Scalar, PvssError> {
+    ark::encode_secret(bytes)
+}
+
+pub fn decode_secret(s: &Scalar) -> Result<Vec<u8>, PvssError> {
+    ark::decode_secret(s)
+}
+
+pub fn encode_scalars(scalars: &[Scalar]) -> Vec<u8> {
+    ark::encode_scalars(scalars)
+}
+
+pub fn decode_scalars(bytes: &[u8]) -> Result<Vec<Scalar>, PvssError> {
+    ark::decode_scalars(bytes)
+}
@@ -0,0 +1,79 @@
+// serde support, through the byte encodings of each type.
+//
+// human readable formats (e.g. JSON) get hex strings, binary formats
+// (e.g. bincode) get the raw bytes. points are compressed.
+use serde::de::{self, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+use super::ark::*;
+
+fn serialize_bytes<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        serializer.serialize_str(&hex::encode(bytes))
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a byte array")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Vec<u8>, E> {
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Vec<u8>, E> {
+        Ok(v)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+        let mut v = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(b) = seq.next_element()? {
+            v.push(b);
+        }
+        Ok(v)
+    }
+}
+
+fn deserialize_bytes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    if deserializer.is_human_readable() {
+        let s = String::deserialize(deserializer)?;
+        hex::decode(&s).map_err(de::Error::custom)
+    } else {
+        deserializer.deserialize_byte_buf(BytesVisitor)
+    }
+}
+
+macro_rules! impl_serde {
+    ($t:ident, $to:expr, $from:expr) => {
+        impl<E: Curve> Serialize for $t<E> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serialize_bytes(&$to(self), serializer)
+            }
+        }
+
+        impl<'de, E: Curve> Deserialize<'de> for $t<E> {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let bytes = deserialize_bytes(deserializer)?;
+                $from(&bytes).map_err(de::Error::custom)
+            }
+        }
+    };
+}
+
+impl_serde!(Scalar, Scalar::to_bytes, Scalar::from_bytes);
+impl_serde!(Point, Point::to_bytes_compressed, Point::from_compressed);
+impl_serde!(
+    PublicKey,
+    |k: &PublicKey<E>| k.point.to_bytes_compressed(),
+    |b: &[u8]| Point::from_compressed(b).map(|point| PublicKey { point })
+);
+impl_serde!(PrivateKey, PrivateKey::to_bytes, PrivateKey::from_bytes);
+impl_serde!(PointG2, PointG2::to_bytes_compressed, PointG2::from_compressed);
@@ -1,8 +1,15 @@
 // DLEQ proof through g^a
 use super::crypto::*;
+use super::error::PvssError;
+use super::transcript::Transcript;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use rand::{rngs::StdRng, SeedableRng};
+use rand::{CryptoRng, RngCore};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
-type Challenge = Scalar;
-
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Clone)]
 pub struct DLEQ {
     pub g1: Point,
@@ -10,26 +17,136 @@ pub struct DLEQ {
     pub g2: Point,
     pub h2: Point,
 }
-#[derive(Clone)]
+
+/// a proof in commitment form: the commitments `a1 = g1 * w`, `a2 = g2 * w`
+/// and the response `z = w + secret * c`. the challenge `c` is recomputed from
+/// the commitments, which lets `batch_verify` fold many proofs together.
+#[derive(Clone, Debug)]
 pub struct Proof {
-    c: Challenge,
+    a1: Point,
+    a2: Point,
     z: Scalar,
 }
 
 impl Proof {
-    pub fn create(w: Scalar, a: Scalar, dleq: DLEQ) -> Proof {
-        let a1 = dleq.g1.mul(&w);
-        let a2 = dleq.g2.mul(&w);
-        let c = Scalar::hash_points(vec![dleq.h1, dleq.h2, a1, a2]);
-        let r = w + a * c.clone();
-        Proof { c, z: r }
+    /// prove that `log_g1(h1) == log_g2(h2)`, where `secret` is the common exponent
+    #[cfg(feature = "std")]
+    pub fn create(secret: &Scalar, g1: &Point, h1: &Point, g2: &Point, h2: &Point) -> Proof {
+        Proof::create_with_nonce(Scalar::generate(), secret, g1, h1, g2, h2)
+    }
+
+    fn create_with_nonce(
+        w: Scalar,
+        a: &Scalar,
+        g1: &Point,
+        h1: &Point,
+        g2: &Point,
+        h2: &Point,
+    ) -> Proof {
+        let a1 = g1.mul(&w);
+        let a2 = g2.mul(&w);
+        let c = challenge(g1, h1, g2, h2, &a1, &a2);
+        let z = w + a * &c;
+        Proof { a1, a2, z }
+    }
+
+    /// check `g1 * z == a1 + h1 * c` and `g2 * z == a2 + h2 * c`
+    pub fn verify(&self, g1: &Point, h1: &Point, g2: &Point, h2: &Point) -> bool {
+        let c = challenge(g1, h1, g2, h2, &self.a1, &self.a2);
+        g1.mul(&self.z) == &self.a1 + &h1.mul(&c) && g2.mul(&self.z) == &self.a2 + &h2.mul(&c)
+    }
+}
+
+// the statement and the commitments `a1 = g1 * w`, `a2 = g2 * w`
+fn challenge(g1: &Point, h1: &Point, g2: &Point, h2: &Point, a1: &Point, a2: &Point) -> Scalar {
+    let mut transcript = Transcript::new(b"dleq");
+    transcript.append_point(b"g1", g1);
+    transcript.append_point(b"h1", h1);
+    transcript.append_point(b"g2", g2);
+    transcript.append_point(b"h2", h2);
+    transcript.append_point(b"a1", a1);
+    transcript.append_point(b"a2", a2);
+    transcript.challenge_scalar(b"c")
+}
+
+// compressed commitments and the canonical response
+const PROOF_BYTES: usize = 48 + 48 + 32;
+
+impl Proof {
+    /// the two compressed commitments followed by the canonical response
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(PROOF_BYTES);
+        buf.extend_from_slice(&self.a1.to_bytes_compressed());
+        buf.extend_from_slice(&self.a2.to_bytes_compressed());
+        buf.extend_from_slice(&self.z.to_bytes_canonical());
+        buf
     }
 
-    pub fn verify(&self, dleq: DLEQ) -> bool {
-        let r1 = dleq.g1.mul(&self.z);
-        let r2 = dleq.g2.mul(&self.z);
-        let a1 = r1 - dleq.h1.mul(&self.c);
-        let a2 = r2 - dleq.h2.mul(&self.c);
-        self.c == Scalar::hash_points(vec![dleq.h1, dleq.h2, a1, a2])
+    pub fn from_bytes(bytes: &[u8]) -> Result<Proof, PvssError> {
+        if bytes.len() != PROOF_BYTES {
+            return Err(PvssError::InvalidPointEncoding);
+        }
+        let mut z = [0u8; 32];
+        z.copy_from_slice(&bytes[96..]);
+        Ok(Proof {
+            a1: Point::from_bytes_compressed(&bytes[..48])?,
+            a2: Point::from_bytes_compressed(&bytes[48..96])?,
+            z: Scalar::from_bytes_canonical(&z)?,
+        })
     }
 }
+
+pub type DleqProof = Proof;
+
+/// prove that `log_g(g * x) == log_h(h * x)` without revealing `x`
+pub fn prove<R: RngCore + CryptoRng>(g: &Point, h: &Point, x: &Scalar, rng: &mut R) -> DleqProof {
+    Proof::create_with_nonce(Scalar::generate_with(rng), x, g, &g.mul(x), h, &h.mul(x))
+}
+
+/// check a proof that `log_g(gx) == log_h(hx)`
+pub fn verify(proof: &DleqProof, g: &Point, h: &Point, gx: &Point, hx: &Point) -> bool {
+    proof.verify(g, gx, h, hx)
+}
+
+/// check many proofs of `log_g1(h1) == log_g2(h2)` for the instances
+/// `(g1, h1, g2, h2)` at once: the equations `g1 * z - h1 * c - a1 == 0` and
+/// `g2 * z - h2 * c - a2 == 0` of every proof are weighted by random scalars
+/// from `rng` and summed in a single multi scalar multiplication. a batch
+/// with any invalid proof fails but with negligible probability.
+pub fn batch_verify<R: RngCore + CryptoRng>(
+    proofs: &[DleqProof],
+    instances: &[(Point, Point, Point, Point)],
+    rng: &mut R,
+) -> bool {
+    if proofs.len() != instances.len() {
+        return false;
+    }
+    #[cfg(feature = "rayon")]
+    let pairs = proofs.par_iter().zip(instances.par_iter());
+    #[cfg(not(feature = "rayon"))]
+    let pairs = proofs.iter().zip(instances.iter());
+    let challenges: Vec<Scalar> = pairs
+        .map(|(proof, (g1, h1, g2, h2))| challenge(g1, h1, g2, h2, &proof.a1, &proof.a2))
+        .collect();
+
+    let mut points = Vec::with_capacity(6 * proofs.len());
+    let mut scalars = Vec::with_capacity(6 * proofs.len());
+    for ((proof, (g1, h1, g2, h2)), c) in proofs.iter().zip(instances).zip(&challenges) {
+        for (g, h, a) in [(g1, h1, &proof.a1), (g2, h2, &proof.a2)] {
+            let r = Scalar::generate_with(rng);
+            points.extend_from_slice(&[g.clone(), h.clone(), a.clone()]);
+            scalars.extend_from_slice(&[&r * &proof.z, -(&r * c), -r]);
+        }
+    }
+    Point::multi_scalar_mul(&points, &scalars).is_identity()
+}
+
+/// `batch_verify` with weights drawn from the operating system, for the
+/// statements `(g1, h1, g2, h2)`
+#[cfg(feature = "std")]
+pub fn batch_verify_dleq(
+    proofs: &[DleqProof],
+    statements: &[(Point, Point, Point, Point)],
+) -> bool {
+    batch_verify(proofs, statements, &mut StdRng::from_entropy())
+}
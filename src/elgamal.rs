@@ -0,0 +1,86 @@
+// ElGamal encryption of points of G1
+use alloc::vec::Vec;
+use rand::{CryptoRng, RngCore};
+
+use super::crypto::*;
+use super::dleq::DleqProof;
+
+/// encrypt `msg` to `pk` as `(G * r, msg + pk * r)` for a fresh random `r`
+pub fn encrypt<R: RngCore + CryptoRng>(pk: &PublicKey, msg: &Point, rng: &mut R) -> (Point, Point) {
+    let r = Scalar::generate_with(rng);
+    (Point::from_scalar(&r), msg + &pk.point.mul(&r))
+}
+
+pub fn decrypt(sk: &PrivateKey, ciphertext: &(Point, Point)) -> Point {
+    let (c1, c2) = ciphertext;
+    c2 - &c1.mul(&sk.scalar)
+}
+
+/// `decrypt`, with a proof that `log_G(pk) == log_c1(c2 - msg)` so that
+/// others can use the decryption without trusting the key owner
+#[cfg(feature = "std")]
+pub fn decrypt_share_with_proof(
+    sk: &PrivateKey,
+    encrypted_share: &(Point, Point),
+) -> (Point, DleqProof) {
+    let (c1, c2) = encrypted_share;
+    let g = Point::generator();
+    let mask = c1.mul(&sk.scalar);
+    let proof = DleqProof::create(&sk.scalar, &g, &g.mul(&sk.scalar), c1, &mask);
+    (c2 - &mask, proof)
+}
+
+pub fn verify_decrypted_share(
+    pk: &PublicKey,
+    encrypted_share: &(Point, Point),
+    decrypted: &Point,
+    proof: &DleqProof,
+) -> bool {
+    let (c1, c2) = encrypted_share;
+    proof.verify(&Point::generator(), &pk.point, c1, &(c2 - decrypted))
+}
+
+/// encrypt a share as `pk * share`, the form used by the pvss dealer
+pub fn encrypt_share(pk: &PublicKey, share: &Scalar) -> Point {
+    pk.point.mul(share)
+}
+
+/// recover `G * share` from `pk * share`
+pub fn decrypt_share(sk: &PrivateKey, encrypted: &Point) -> Point {
+    encrypted.mul(&sk.scalar.inverse())
+}
+
+/// the recipients of many dealings, with a table of multiples of each public
+/// key so that `encrypt_shares` costs a fixed base multiplication per share.
+///
+/// the tables take memory per key, as given by `GeneratorTable`: about 850 KB
+/// each with the default window, so 850 MB for a committee of 1000. use
+/// `with_window` with a smaller window for large committees, or
+/// `encrypt_share` without a context when few dealings are made.
+pub struct EncryptionContext {
+    tables: Vec<GeneratorTable>,
+}
+
+impl EncryptionContext {
+    pub fn new(pubkeys: &[PublicKey]) -> EncryptionContext {
+        Self::with_window(pubkeys, TABLE_WINDOW)
+    }
+
+    /// a context whose tables have a window of `window` bits. panics if
+    /// `window` is 0.
+    pub fn with_window(pubkeys: &[PublicKey], window: usize) -> EncryptionContext {
+        EncryptionContext {
+            tables: pubkeys
+                .iter()
+                .map(|pk| GeneratorTable::with_window(&pk.point, window))
+                .collect(),
+        }
+    }
+
+    /// `encrypt_share` of each share to the recipient at the same position.
+    /// panics if there are not as many shares as recipients.
+    pub fn encrypt_shares(&self, shares: &[Scalar]) -> Vec<Point> {
+        assert_eq!(shares.len(), self.tables.len(), "one share per recipient");
+        self.tables.iter().zip(shares).map(|(table, share)| table.mul(share)).collect()
+    }
+}
@@ -0,0 +1,80 @@
+// Errors reported by the library
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PvssError {
+    /// the bytes cannot be read as a point: wrong length or bad coordinates
+    InvalidPointEncoding,
+    /// the bytes cannot be read as a scalar: wrong length or out of range
+    InvalidScalarEncoding,
+    /// the point does not satisfy the curve equation
+    NotOnCurve,
+    /// the point is not in the prime order subgroup
+    NotInSubgroup,
+    /// the threshold is zero or above the number of participants
+    InvalidThreshold,
+    /// there are no participants to share between
+    NoParticipants,
+    /// fewer shares than the threshold were given
+    NotEnoughShares,
+    /// the same participant index appears twice
+    DuplicateIndex,
+    /// a share has the index 0, which belongs to the secret
+    ZeroIndex,
+    /// a participant index does not fit in a u32
+    IndexTooLarge,
+    /// a dealing does not verify against the participants
+    InvalidDealing,
+    /// the bytes do not fit in a scalar
+    SecretTooLong,
+}
+
+impl fmt::Display for PvssError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PvssError::InvalidPointEncoding => write!(f, "invalid point encoding"),
+            PvssError::InvalidScalarEncoding => write!(f, "invalid scalar encoding"),
+            PvssError::NotOnCurve => write!(f, "point not on the curve"),
+            PvssError::NotInSubgroup => write!(f, "point not in the prime order subgroup"),
+            PvssError::InvalidThreshold => write!(f, "invalid threshold"),
+            PvssError::NoParticipants => write!(f, "no participants"),
+            PvssError::NotEnoughShares => write!(f, "not enough shares"),
+            PvssError::DuplicateIndex => write!(f, "duplicate participant index"),
+            PvssError::ZeroIndex => write!(f, "participant index zero"),
+            PvssError::IndexTooLarge => write!(f, "participant index too large"),
+            PvssError::InvalidDealing => write!(f, "invalid dealing"),
+            PvssError::SecretTooLong => write!(f, "secret too long for a scalar"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for PvssError {}
+
+/// why `Dealing::verify_share` rejected a share
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerifyError {
+    /// the threshold does not match the number of commitments
+    InvalidDealing,
+    /// no share or public key has this index, from 1 to n
+    IndexOutOfRange { index: usize },
+    /// the share of this index is not the encryption of its committed value
+    InvalidProof { index: usize },
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VerifyError::InvalidDealing => write!(f, "invalid dealing"),
+            VerifyError::IndexOutOfRange { index } => {
+                write!(f, "share index {} out of range", index)
+            }
+            VerifyError::InvalidProof { index } => write!(f, "invalid proof for share {}", index),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for VerifyError {}
@@ -1,62 +1,2090 @@
+// without the `std` feature the library only needs `alloc`. the simple and
+// scrape schemes draw their randomness from the os and need `std`.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+pub mod bls;
 pub mod crypto;
 
-mod dleq;
-mod math;
-mod pdleq;
-pub mod scrape;
-pub mod simple;
-mod types;
+pub mod dleq;
+pub mod elgamal;
+pub mod error;
+pub mod math;
+#[cfg(feature = "std")]
+mod pdleq;
+pub mod pedersen;
+pub mod pvss;
+pub mod schnorr;
+#[cfg(feature = "std")]
+pub mod scrape;
+pub mod sharing;
+#[cfg(feature = "std")]
+pub mod simple;
+pub mod transcript;
+#[cfg(feature = "std")]
+mod types;
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::bls;
+    use super::crypto;
+    use super::crypto::*;
+    use super::dleq;
+    use super::elgamal;
+    use super::math;
+    use super::pedersen;
+    use super::pvss;
+    use super::schnorr::SchnorrProof;
+    use super::scrape;
+    use super::sharing;
+    use super::simple;
+    use super::transcript::Transcript;
+
+    pub const NB_TESTS: usize = 100;
+
+    fn hex_bytes(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn crypto_point_add_identity() {
+        for _ in 0..NB_TESTS {
+            let i = Scalar::generate();
+            let p = Point::from_scalar(&i);
+            assert!(p.clone() + Point::infinity() == p);
+        }
+    }
+
+    #[test]
+    fn crypto_point_generator() {
+        let g = Point::generator();
+        for _ in 0..NB_TESTS {
+            let i = Scalar::generate();
+            let p1 = Point::from_scalar(&i);
+            let p2 = g.mul(&i);
+            assert!(p1 == p2);
+        }
+    }
+
+    #[test]
+    fn crypto_generate_with_rng() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        let mut rng1 = ChaCha20Rng::from_seed([3; 32]);
+        let mut rng2 = ChaCha20Rng::from_seed([3; 32]);
+        for _ in 0..NB_TESTS {
+            assert!(Scalar::generate_with(&mut rng1) == Scalar::generate_with(&mut rng2));
+        }
+        let (public1, private1) = crypto::create_keypair_with(&mut rng1);
+        let (public2, private2) = crypto::create_keypair_with(&mut rng2);
+        assert!(public1 == public2 && private1 == private2);
+        assert!(Scalar::generate_with(&mut rng1) != Scalar::generate_with(&mut rng1));
+    }
+
+    #[test]
+    fn crypto_rand_seeded() {
+        use rand::{RngCore, SeedableRng};
+        use rand_chacha::ChaCha20Rng;
+
+        let seeded = || ChaCha20Rng::from_seed([4; 32]);
+        let (public1, private1) = crypto::create_keypair_with_rng(&mut seeded());
+        let (public2, private2) = crypto::create_keypair_with_rng(&mut seeded());
+        assert!(public1 == public2 && private1 == private2);
+        assert!(Point::from_scalar(&private1.scalar) == public1.point);
+
+        // unsized rngs work as well
+        let mut rng = seeded();
+        let dyn_rng: &mut dyn RngCore = &mut rng;
+        assert!(Scalar::rand(dyn_rng) == private1.scalar);
+        let s = Scalar::rand(&mut ChaCha20Rng::from_seed([5; 32]));
+        assert!(s == Scalar::generate_with(&mut ChaCha20Rng::from_seed([5; 32])));
+        assert!(s != private1.scalar);
+    }
+
+    #[test]
+    fn crypto_scalar_powers() {
+        let one = Scalar::multiplicative_identity();
+        for _ in 0..NB_TESTS {
+            let a = Scalar::generate();
+            assert!(a.square() == a.clone() * a.clone());
+            let mut power = one.clone();
+            for e in 0..20u64 {
+                assert!(a.pow_u64(e) == power);
+                assert!(a.pow_scalar(&Scalar::from_u32(e as u32)) == power);
+                power *= a.clone();
+            }
+            // Fermat: a^(r - 1) == 1 and a^(r - 2) == 1 / a
+            let r_minus_one = -one.clone();
+            assert!(a.pow_scalar(&r_minus_one) == one);
+            assert!(a.pow_scalar(&(r_minus_one - one.clone())) == a.inverse());
+        }
+        assert!(Scalar::from_u32(2).pow_u64(40) == Scalar::from_u32(1 << 20).square());
+        let three = Scalar::from_u32(3);
+        assert!(three.pow_u64(1 << 40) == three.pow_scalar(&Scalar::from_u32(1 << 20).square()));
+    }
+
+    #[test]
+    fn crypto_random_nonzero() {
+        use rand::{CryptoRng, RngCore, SeedableRng};
+        use rand_chacha::ChaCha20Rng;
+
+        // an rng whose first outputs are all zero, which `Scalar::rand` maps
+        // to the zero scalar
+        struct ZerosFirst {
+            zeros: usize,
+            inner: ChaCha20Rng,
+        }
+        impl RngCore for ZerosFirst {
+            fn next_u32(&mut self) -> u32 {
+                self.next_u64() as u32
+            }
+            fn next_u64(&mut self) -> u64 {
+                if self.zeros > 0 {
+                    self.zeros -= 1;
+                    0
+                } else {
+                    self.inner.next_u64()
+                }
+            }
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                for chunk in dest.chunks_mut(8) {
+                    let word = self.next_u64().to_le_bytes();
+                    chunk.copy_from_slice(&word[..chunk.len()]);
+                }
+            }
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+                self.fill_bytes(dest);
+                Ok(())
+            }
+        }
+        impl CryptoRng for ZerosFirst {}
+
+        let zeros = || ZerosFirst {
+            zeros: 8,
+            inner: ChaCha20Rng::from_seed([16; 32]),
+        };
+        assert!(Scalar::rand(&mut zeros()).is_zero());
+        let s = Scalar::random_nonzero(&mut zeros());
+        assert!(!s.is_zero());
+        let (_, private) = crypto::create_keypair_with(&mut zeros());
+        assert!(private.scalar == s);
+
+        let mut rng = ChaCha20Rng::from_seed([17; 32]);
+        let one = Scalar::multiplicative_identity();
+        for _ in 0..10 * NB_TESTS {
+            let s = Scalar::random_nonzero(&mut rng);
+            assert!(!s.is_zero());
+            assert!(s.clone() * s.inverse() == one);
+        }
+    }
+
+    #[test]
+    fn crypto_keypair_from_seed() {
+        let (public1, private1) = crypto::create_keypair_from_seed(&[7; 32]);
+        let (public2, private2) = crypto::create_keypair_from_seed(&[7; 32]);
+        assert!(public1 == public2 && private1 == private2);
+        assert!(Point::from_scalar(&private1.scalar) == public1.point);
+        assert!(Scalar::generate_from_seed(&[7; 32]) == private1.scalar);
+
+        let (public3, private3) = crypto::create_keypair_from_seed(&[8; 32]);
+        assert!(public3 != public1 && private3 != private1);
+
+        // the derivation must not change between versions
+        let (public, private) = crypto::create_keypair_from_seed(&[0; 32]);
+        assert_eq!(
+            private.to_hex(),
+            "667f299371543411efc56778926308e53660dc88b838c85a974f184840cbd64a"
+        );
+        assert_eq!(
+            hex::encode(public.point.to_bytes_compressed()),
+            "5bc7bfce0c67f889b37a64932c9807fe05e890a29011cc56f4881b63eb5eabf5\
+             00929b113ad1a34ca7377b9e8cea960a"
+        );
+        let again = crypto::create_keypair_from_seed(&[0; 32]);
+        assert_eq!(again.0.to_bytes(), public.to_bytes());
+        assert_eq!(again.1.to_bytes(), private.to_bytes());
+    }
+
+    #[test]
+    fn crypto_aggregate_public_keys() {
+        let keys: Vec<PublicKey> = (0..10).map(|_| crypto::create_keypair().0).collect();
+        assert!(PublicKey::aggregate(&keys[..1]) == keys[0]);
+        assert!(PublicKey::aggregate(&[]).point.is_identity());
+
+        let sum = PublicKey::aggregate(&keys);
+        let mut reversed = keys.clone();
+        reversed.reverse();
+        assert!(PublicKey::aggregate(&reversed) == sum);
+        let manual = keys
+            .iter()
+            .fold(Point::infinity(), |acc, k| acc + k.point.clone());
+        assert!(sum.point == manual);
+
+        let weights: Vec<Scalar> = (0..10).map(|_| Scalar::generate()).collect();
+        let points: Vec<Point> = keys.iter().map(|k| k.point.clone()).collect();
+        let weighted = PublicKey::aggregate_weighted(&keys, &weights);
+        assert!(weighted.point == Point::multi_scalar_mul(&points, &weights));
+        let ones = vec![Scalar::from_u32(1); 10];
+        assert!(PublicKey::aggregate_weighted(&keys, &ones) == sum);
+        assert!(PublicKey::aggregate_weighted(&[], &[]).point.is_identity());
+    }
+
+    #[test]
+    fn crypto_keypair_rejects_zero() {
+        assert_eq!(
+            crypto::create_keypair_from_scalar(Scalar::zero()).err(),
+            Some(KeyError::ZeroScalar)
+        );
+        assert_eq!(Point::from_scalar_checked(&Scalar::zero()), Err(KeyError::ZeroScalar));
+        assert!(Point::from_scalar(&Scalar::zero()) == Point::infinity());
+
+        let s = Scalar::from_u32(5);
+        let (public, private) = crypto::create_keypair_from_scalar(s.clone()).unwrap();
+        assert!(private.scalar == s);
+        assert!(public.point == Point::from_scalar_checked(&s).unwrap());
+        for _ in 0..NB_TESTS {
+            let (_, private) = crypto::create_keypair();
+            assert!(!private.scalar.is_zero());
+        }
+    }
+
+    #[test]
+    fn crypto_zero_neg_default() {
+        assert!(Scalar::zero() == Scalar::from_u32(0));
+        assert!(-Scalar::zero() == Scalar::zero());
+        for _ in 0..NB_TESTS {
+            let s = Scalar::generate();
+            assert!(s.clone() + (-s.clone()) == Scalar::zero());
+            assert!(s.clone() - s.clone() == Scalar::zero());
+            assert!(s.clone() + Scalar::zero() == s);
+            assert!(!s.is_zero());
+
+            let p = Point::from_scalar(&s);
+            assert!(p.clone() + (-p.clone()) == Point::infinity());
+            assert!((p.clone() - p.clone()).is_identity());
+            assert!(-p.clone() == Point::from_scalar(&-s.clone()));
+            assert!(!p.is_identity());
+        }
+        assert!(Scalar::default().is_zero());
+        assert!(Point::default().is_identity());
+    }
+
+    #[test]
+    fn crypto_reference_ops() {
+        for _ in 0..NB_TESTS {
+            let a = Scalar::generate();
+            let b = Scalar::generate();
+            assert!(&a + &b == a.clone() + b.clone());
+            assert!(&a - &b == a.clone() - b.clone());
+            assert!(&a * &b == a.clone() * b.clone());
+
+            let p = Point::from_scalar(&a);
+            let q = Point::from_scalar(&b);
+            assert!(&p + &q == p.clone() + q.clone());
+            assert!(&p - &q == p.clone() - q.clone());
+
+            assert!(a.clone() + &b == a.clone() + b.clone());
+            assert!(a.clone() - &b == a.clone() - b.clone());
+            assert!(a.clone() * &b == a.clone() * b.clone());
+            assert!(p.clone() + &q == p.clone() + q.clone());
+            assert!(p.clone() - &q == p.clone() - q.clone());
+            assert!(&p * &b == p.mul(&b));
+            assert!(&p * b.clone() == p.mul(&b));
+            assert!(&Point::generator() * &a == p);
+        }
+    }
+
+    #[test]
+    fn crypto_assign_ops() {
+        let scalars: Vec<Scalar> = (0..NB_TESTS).map(|_| Scalar::generate()).collect();
+        let points: Vec<Point> = scalars.iter().map(Point::from_scalar).collect();
+
+        let mut sum = Scalar::zero();
+        let mut diff = Scalar::zero();
+        let mut product = Scalar::multiplicative_identity();
+        let mut point_sum = Point::infinity();
+        let mut point_diff = Point::infinity();
+        for (s, p) in scalars.iter().zip(points.iter()) {
+            sum += s.clone();
+            diff -= s.clone();
+            product *= s.clone();
+            point_sum += p.clone();
+            point_diff -= p.clone();
+        }
+
+        let chain = |f: fn(Scalar, Scalar) -> Scalar, init: Scalar| {
+            scalars.iter().fold(init, |acc, s| f(acc, s.clone()))
+        };
+        assert!(sum == chain(|a, b| a + b, Scalar::zero()));
+        assert!(diff == chain(|a, b| a - b, Scalar::zero()));
+        assert!(product == chain(|a, b| a * b, Scalar::multiplicative_identity()));
+        assert!(point_sum == points.iter().fold(Point::infinity(), |acc, p| acc + p.clone()));
+        assert!(point_diff == points.iter().fold(Point::infinity(), |acc, p| acc - p.clone()));
+        assert!(point_sum == Point::from_scalar(&sum));
+    }
+
+    #[test]
+    fn crypto_sum_product() {
+        assert!(Vec::<Scalar>::new().into_iter().sum::<Scalar>() == Scalar::zero());
+        assert!(
+            Vec::<Scalar>::new().into_iter().product::<Scalar>()
+                == Scalar::multiplicative_identity()
+        );
+        assert!(Vec::<Point>::new().into_iter().sum::<Point>() == Point::infinity());
+
+        let scalars: Vec<Scalar> = (0..NB_TESTS).map(|_| Scalar::generate()).collect();
+        let points: Vec<Point> = scalars.iter().map(Point::from_scalar).collect();
+        let sum = scalars.iter().fold(Scalar::zero(), |acc, s| acc + s.clone());
+        let product = scalars
+            .iter()
+            .fold(Scalar::multiplicative_identity(), |acc, s| acc * s.clone());
+        assert!(scalars.iter().sum::<Scalar>() == sum);
+        assert!(scalars.clone().into_iter().sum::<Scalar>() == sum);
+        assert!(scalars.iter().product::<Scalar>() == product);
+        assert!(scalars.clone().into_iter().product::<Scalar>() == product);
+        assert!(points.iter().sum::<Point>() == Point::from_scalar(&sum));
+        assert!(points.into_iter().sum::<Point>() == Point::from_scalar(&sum));
+    }
+
+    #[test]
+    fn crypto_scalar_div() {
+        for _ in 0..NB_TESTS {
+            let a = Scalar::generate();
+            let b = Scalar::generate();
+            let q = a.clone() / b.clone();
+            assert!(q.clone() * b.clone() == a);
+            assert!(a.checked_div(&b).unwrap() == q);
+        }
+        assert!(Scalar::from_u32(6) / Scalar::from_u32(3) == Scalar::from_u32(2));
+        assert!(Scalar::generate().checked_div(&Scalar::zero()).is_none());
+    }
+
+    #[test]
+    fn crypto_scalar_assign_ops() {
+        let scalars: Vec<Scalar> = (0..NB_TESTS).map(|_| Scalar::generate()).collect();
+        let mut sum = Scalar::zero();
+        let mut product = Scalar::multiplicative_identity();
+        for s in scalars.iter() {
+            sum += s.clone();
+            product *= s.clone();
+        }
+        assert!(sum == scalars.iter().sum::<Scalar>());
+        assert!(product == scalars.iter().product::<Scalar>());
+        for s in scalars.iter() {
+            sum -= s.clone();
+            product /= s.clone();
+        }
+        assert!(sum.is_zero());
+        assert!(product == Scalar::multiplicative_identity());
+
+        let (a, b) = (&scalars[0], &scalars[1]);
+        assert!(&(a / b) * b == *a);
+    }
+
+    #[test]
+    #[should_panic(expected = "division by zero scalar")]
+    fn crypto_scalar_div_assign_by_zero() {
+        let mut a = Scalar::generate();
+        a /= Scalar::zero();
+    }
+
+    #[test]
+    #[should_panic(expected = "division by zero scalar")]
+    fn crypto_scalar_div_by_zero() {
+        let _ = Scalar::generate() / Scalar::zero();
+    }
+
+    #[test]
+    fn crypto_scalar_canonical_bytes() {
+        use super::error::PvssError;
+        use ark_bls12_381::FrParameters;
+        use ark_ff::{BigInteger, BigInteger256, FpParameters, ToBytes};
+
+        fn le_bytes(n: BigInteger256) -> [u8; 32] {
+            let mut out = [0u8; 32];
+            n.write(&mut out[..]).unwrap();
+            out
+        }
+        let one = BigInteger256::from(1);
+        let order = FrParameters::MODULUS;
+        let mut below = order;
+        below.sub_noborrow(&one);
+        let mut above = order;
+        above.add_nocarry(&one);
+
+        let max = Scalar::from_bytes_canonical(&le_bytes(below)).unwrap();
+        assert!(max == -Scalar::multiplicative_identity());
+        assert_eq!(max.to_bytes_canonical(), le_bytes(below));
+        for n in [order, above] {
+            assert_eq!(
+                Scalar::from_bytes_canonical(&le_bytes(n)).err(),
+                Some(PvssError::InvalidScalarEncoding)
+            );
+        }
+        assert!(Scalar::from_bytes_canonical(&[0xff; 32]).is_err());
+
+        for _ in 0..1000 {
+            let s = Scalar::generate();
+            let bytes = s.to_bytes_canonical();
+            assert_eq!(&bytes[..], &s.to_bytes()[..]);
+            assert!(Scalar::from_bytes_canonical(&bytes).unwrap() == s);
+        }
+    }
+
+    #[test]
+    fn crypto_scalar_from_bytes_mod_order() {
+        use ark_bls12_381::FrParameters;
+        use ark_ff::{BigInteger, BigInteger256, FpParameters, ToBytes};
+
+        fn le_bytes(n: BigInteger256) -> [u8; 32] {
+            let mut out = [0u8; 32];
+            n.write(&mut out[..]).unwrap();
+            out
+        }
+        // 5 and order + 5 are the same residue
+        let mut above = FrParameters::MODULUS;
+        above.add_nocarry(&BigInteger256::from(5));
+        let five = Scalar::from_u32(5);
+        assert!(Scalar::from_bytes_mod_order(&le_bytes(above)) == five);
+        assert!(Scalar::from_bytes_mod_order(&le_bytes(BigInteger256::from(5))) == five);
+        assert!(Scalar::from_bytes_mod_order(&[5]) == five);
+        assert!(Scalar::from_bytes_mod_order(&[]) == Scalar::zero());
+        assert!(Scalar::from_bytes_mod_order(&le_bytes(FrParameters::MODULUS)) == Scalar::zero());
+
+        let max = Scalar::from_bytes_mod_order(&[0xff; 32]);
+        assert!(Scalar::from_bytes_canonical(&max.to_bytes_canonical()).unwrap() == max);
+        // 2^256 * 5 + 5
+        let mut wide = vec![5u8];
+        wide.resize(32, 0);
+        wide.push(5);
+        let two_256 = Scalar::from_bytes_mod_order(&[&[0u8; 32][..], &[1]].concat());
+        assert!(Scalar::from_bytes_mod_order(&wide) == five.clone() * two_256 + five);
+    }
+
+    #[test]
+    fn crypto_batch_inverse() {
+        let scalars: Vec<Scalar> = (0..NB_TESTS).map(|_| Scalar::generate()).collect();
+        let inverses = Scalar::batch_inverse(&scalars);
+        assert_eq!(inverses.len(), scalars.len());
+        for (s, i) in scalars.iter().zip(inverses.iter()) {
+            assert!(s.inverse() == *i);
+        }
+
+        let zero = Scalar::from_u32(0);
+        let a = Scalar::generate();
+        let inverses = Scalar::batch_inverse(&[a.clone(), zero.clone(), a.clone()]);
+        assert!(inverses[0] == a.inverse());
+        assert!(inverses[1] == zero);
+        assert!(inverses[2] == a.inverse());
+        assert!(Scalar::batch_inverse(&[]).is_empty());
+    }
+
+    #[test]
+    fn crypto_constant_time_eq() {
+        use subtle::{Choice, ConstantTimeEq};
+
+        let (_, private) = crypto::create_keypair();
+        let (_, other) = crypto::create_keypair();
+        let copy = PrivateKey::from_bytes(&private.to_bytes()).unwrap();
+        assert!(bool::from(private.ct_eq(&copy)));
+        assert!(!bool::from(private.ct_eq(&other)));
+        assert!(private == copy && private != other);
+
+        let s = Scalar::generate();
+        assert!(bool::from(s.ct_eq(&s.clone())));
+        assert!(!bool::from(s.ct_eq(&Scalar::generate())));
+        for _ in 0..NB_TESTS {
+            let (a, b) = (Scalar::generate(), Scalar::generate());
+            assert_eq!(bool::from(a.ct_eq(&b)), a == b);
+            assert!(bool::from(a.ct_eq(&Scalar::from_bytes(&a.to_bytes()).unwrap())));
+            assert!(Scalar::conditional_select(&a, &b, Choice::from(0)) == a);
+            assert!(Scalar::conditional_select(&a, &b, Choice::from(1)) == b);
+        }
+    }
+
+    #[test]
+    fn crypto_multi_scalar_mul() {
+        let points: Vec<Point> = (0..64)
+            .map(|_| Point::from_scalar(&Scalar::generate()))
+            .collect();
+        let scalars: Vec<Scalar> = (0..64).map(|_| Scalar::generate()).collect();
+        let naive = points
+            .iter()
+            .zip(scalars.iter())
+            .fold(Point::infinity(), |acc, (p, s)| acc + p.mul(s));
+        assert!(Point::multi_scalar_mul(&points, &scalars) == naive);
+        assert!(Point::multi_scalar_mul(&[], &[]) == Point::infinity());
+    }
+
+    #[test]
+    #[should_panic(expected = "different number of points and scalars")]
+    fn crypto_multi_scalar_mul_length_mismatch() {
+        Point::multi_scalar_mul(&[Point::generator()], &[]);
+    }
+
+    #[test]
+    fn crypto_batch_to_bytes() {
+        let mut points: Vec<Point> = (0..NB_TESTS)
+            .map(|_| Point::from_scalar(&Scalar::generate()))
+            .collect();
+        points.push(Point::infinity());
+        let bytes = Point::batch_to_bytes(&points);
+        assert_eq!(bytes.len(), points.len());
+        for (p, b) in points.iter().zip(bytes.iter()) {
+            assert_eq!(&p.to_bytes(), b);
+        }
+        assert!(Point::batch_to_bytes(&[]).is_empty());
+    }
+
+    #[test]
+    fn crypto_point_compressed() {
+        let mut points: Vec<Point> = (0..NB_TESTS)
+            .map(|_| Point::from_scalar(&Scalar::generate()))
+            .collect();
+        points.push(Point::infinity());
+        for p in points.iter() {
+            let bytes = p.to_bytes_compressed();
+            assert_eq!(bytes.len(), 48);
+            assert!(Point::from_bytes_compressed(&bytes).unwrap() == *p);
+            assert!(Point::from_bytes(&p.to_bytes()).unwrap() == *p);
+        }
+        let bytes = points[0].to_bytes_compressed();
+        assert!(Point::from_bytes_compressed(&bytes[1..]).is_err());
+        assert!(Point::from_bytes_compressed(&points[0].to_bytes()).is_err());
+    }
+
+    #[test]
+    fn crypto_point_uncompressed() {
+        let mut points: Vec<Point> = (0..NB_TESTS)
+            .map(|_| Point::from_scalar(&Scalar::generate()))
+            .collect();
+        points.push(Point::infinity());
+        for p in points.iter() {
+            let bytes = p.to_bytes_uncompressed();
+            assert_eq!(bytes.len(), 97);
+            let q = Point::from_bytes_uncompressed(&bytes).unwrap();
+            assert!(q == Point::from_bytes_compressed(&p.to_bytes_compressed()).unwrap());
+            assert!(q == *p);
+        }
+        let bytes = points[0].to_bytes_uncompressed();
+        assert!(Point::from_bytes_uncompressed(&bytes[1..]).is_err());
+        assert!(Point::from_bytes_uncompressed(&points[0].to_bytes_compressed()).is_err());
+    }
+
+    #[test]
+    fn crypto_write_read_stream() {
+        use std::io::Cursor;
+
+        let points: Vec<Point> = (0..NB_TESTS)
+            .map(|_| Point::from_scalar(&Scalar::generate()))
+            .collect();
+        let scalars: Vec<Scalar> = (0..NB_TESTS).map(|_| Scalar::generate()).collect();
+        let mut cursor = Cursor::new(Vec::with_capacity(NB_TESTS * (97 + 32)));
+        for (p, s) in points.iter().zip(scalars.iter()) {
+            p.write_to(&mut cursor).unwrap();
+            s.write_to(&mut cursor).unwrap();
+        }
+        let buf = cursor.into_inner();
+        assert_eq!(buf.len(), NB_TESTS * (97 + 32));
+        assert_eq!(&buf[..97], &points[0].to_bytes()[..]);
+
+        let mut cursor = Cursor::new(buf);
+        for (p, s) in points.iter().zip(scalars.iter()) {
+            assert!(Point::read_from(&mut cursor).unwrap() == *p);
+            assert!(Scalar::read_from(&mut cursor).unwrap() == *s);
+        }
+        assert!(Point::read_from(&mut cursor).is_err());
+        assert!(Scalar::read_from(&mut Cursor::new(vec![0u8; 31])).is_err());
+        assert!(Scalar::read_from(&mut Cursor::new(vec![0xffu8; 32])).is_err());
+    }
+
+    #[test]
+    fn crypto_encode_secret() {
+        use super::error::PvssError;
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        let key: Vec<u8> = (0..16).collect();
+        let s = crypto::encode_secret(&key).unwrap();
+        assert_eq!(crypto::decode_secret(&s).unwrap(), key);
+        let empty = crypto::encode_secret(&[]).unwrap();
+        assert!(empty.is_zero());
+        assert_eq!(crypto::decode_secret(&empty).unwrap(), Vec::<u8>::new());
+
+        // 30 bytes and the length byte stay below the 255 bit group order
+        let longest = [0xff; 30];
+        let s = crypto::encode_secret(&longest).unwrap();
+        assert_eq!(crypto::decode_secret(&s).unwrap(), longest.to_vec());
+        assert_eq!(crypto::encode_secret(&[0xff; 31]).err(), Some(PvssError::SecretTooLong));
+        assert_eq!(crypto::encode_secret(&[0; 64]).err(), Some(PvssError::SecretTooLong));
+
+        // most scalars are not encoded secrets
+        assert!(crypto::decode_secret(&Scalar::from_u32(0x0100)).is_err());
+        assert!(crypto::decode_secret(&Scalar::from_u32(31)).is_err());
+        assert_eq!(crypto::decode_secret(&Scalar::from_u32(0x0101)).unwrap(), vec![1]);
+        assert!(crypto::decode_secret(&-Scalar::multiplicative_identity()).is_err());
+
+        // the secret survives sharing
+        let mut rng = ChaCha20Rng::from_seed([18; 32]);
+        let secret = crypto::encode_secret(&key).unwrap();
+        let shares = sharing::split_secret(&secret, 2, 3, &mut rng).unwrap();
+        let recovered = sharing::reconstruct(&shares[1..], 2).unwrap();
+        assert_eq!(crypto::decode_secret(&recovered).unwrap(), key);
+    }
+
+    #[test]
+    fn crypto_encode_vectors() {
+        use super::error::PvssError;
+
+        let empty = crypto::encode_points(&[]);
+        assert_eq!(empty, vec![0, 0, 0, 0]);
+        assert!(crypto::decode_points(&empty).unwrap().is_empty());
+        assert!(crypto::decode_scalars(&crypto::encode_scalars(&[])).unwrap().is_empty());
+
+        let points: Vec<Point> = (0..1000)
+            .map(|i| Point::generator().mul(&Scalar::from_u32(i)))
+            .collect();
+        let bytes = crypto::encode_points(&points);
+        assert_eq!(bytes.len(), 4 + 1000 * (4 + 97));
+        assert!(crypto::decode_points(&bytes).unwrap() == points);
+        let scalars: Vec<Scalar> = (0..1000).map(Scalar::from_u32).collect();
+        let bytes = crypto::encode_scalars(&scalars);
+        assert!(crypto::decode_scalars(&bytes).unwrap() == scalars);
+
+        // compressed elements are accepted too
+        let mut mixed = vec![2, 0, 0, 0, 48, 0, 0, 0];
+        mixed.extend_from_slice(&points[7].to_bytes_compressed());
+        mixed.extend_from_slice(&[97, 0, 0, 0]);
+        mixed.extend_from_slice(&points[8].to_bytes());
+        assert!(crypto::decode_points(&mixed).unwrap() == points[7..9].to_vec());
+
+        // a huge declared count, truncated or trailing bytes
+        let huge = [0xff, 0xff, 0xff, 0xff, 32, 0, 0, 0];
+        assert_eq!(crypto::decode_points(&huge), Err(PvssError::InvalidPointEncoding));
+        assert_eq!(crypto::decode_scalars(&huge), Err(PvssError::InvalidScalarEncoding));
+        assert!(crypto::decode_scalars(&bytes[..bytes.len() - 1]).is_err());
+        assert!(crypto::decode_scalars(&[bytes.clone(), vec![0]].concat()).is_err());
+        assert!(crypto::decode_points(&[0, 0, 0]).is_err());
+        assert!(crypto::decode_points(&[1, 0, 0, 0, 0xff, 0xff, 0xff, 0xff]).is_err());
+    }
+
+    #[test]
+    fn crypto_hex() {
+        for _ in 0..NB_TESTS {
+            let s = Scalar::generate();
+            let p = Point::from_scalar(&s);
+            assert_eq!(s.to_hex().len(), 64);
+            assert_eq!(p.to_hex().len(), 2 * 97);
+            assert!(Scalar::from_hex(&s.to_hex()).unwrap() == s);
+            assert!(Point::from_hex(&p.to_hex()).unwrap() == p);
+        }
+        let (public, private) = crypto::create_keypair();
+        assert_eq!(public.to_hex(), public.point.to_hex());
+        assert_eq!(private.to_hex(), private.scalar.to_hex());
+
+        let s = Scalar::generate().to_hex();
+        let p = Point::generator().to_hex();
+        assert_eq!(Scalar::from_hex(&s[1..]).err(), Some(DecodeError::InvalidHex));
+        assert_eq!(Scalar::from_hex(&s.replace('0', "x")).err(), Some(DecodeError::InvalidHex));
+        assert_eq!(Scalar::from_hex(&s[2..]).err(), Some(DecodeError::InvalidLength));
+        assert_eq!(Scalar::from_hex(&"ff".repeat(32)).err(), Some(DecodeError::InvalidScalar));
+        assert_eq!(Point::from_hex(&p[..p.len() - 1]).err(), Some(DecodeError::InvalidHex));
+        assert_eq!(Point::from_hex("0g").err(), Some(DecodeError::InvalidHex));
+        assert_eq!(Point::from_hex("").err(), Some(DecodeError::InvalidLength));
+    }
+
+    #[test]
+    fn crypto_keys_hex_base64() {
+        use super::crypto::DecodeError;
+        use super::error::PvssError;
+
+        let (public, private) = crypto::create_keypair_from_seed(&[3; 32]);
+        let hex = public.to_hex();
+        assert_eq!(hex.len(), 2 * 97);
+        assert_eq!(hex_bytes(&hex), public.to_bytes());
+        assert!(PublicKey::from_hex(&hex).unwrap() == public);
+        assert!(PublicKey::from_hex(&hex.to_uppercase()).unwrap() == public);
+        assert!(PublicKey::from_base64(&public.to_base64()).unwrap() == public);
+        assert!(PrivateKey::from_hex(&private.to_hex()).unwrap() == private);
+        assert!(PrivateKey::from_base64(&private.to_base64()).unwrap() == private);
+
+        // bad hex is the same error for keys as for points and scalars
+        let bad_hex = Err(DecodeError::InvalidHex);
+        assert_eq!(PublicKey::from_hex(&hex[1..]).map(|_| ()), bad_hex);
+        assert_eq!(PublicKey::from_hex(&format!("zz{}", &hex[2..])).map(|_| ()), bad_hex);
+        assert_eq!(Point::from_hex(&hex[1..]).map(|_| ()), bad_hex);
+        assert_eq!(PrivateKey::from_hex("abc").map(|_| ()), bad_hex);
+        assert_eq!(PrivateKey::from_hex(&"g".repeat(64)).map(|_| ()), bad_hex);
+        assert_eq!(Scalar::from_hex("abc").map(|_| ()), bad_hex);
+        assert!(PublicKey::from_hex(&hex[2..]).is_err());
+        assert!(PrivateKey::from_hex(&"ff".repeat(32)).is_err());
+        // right length, but not a point of the curve
+        let mut bytes = public.to_bytes();
+        bytes[0] ^= 1;
+        let not_on_curve = Err(DecodeError::NotOnCurve);
+        assert_eq!(PublicKey::from_hex(&hex::encode(&bytes)).map(|_| ()), not_on_curve);
+
+        let invalid = Err(PvssError::InvalidPointEncoding);
+        assert_eq!(PublicKey::from_base64("not base64!").map(|_| ()), invalid);
+        let invalid = Err(PvssError::InvalidScalarEncoding);
+        assert_eq!(PrivateKey::from_base64("AAAA").map(|_| ()), invalid);
+    }
+
+    #[test]
+    fn crypto_zero_identity() {
+        assert!(Scalar::zero().is_zero());
+        assert!((Scalar::from_u32(3) - Scalar::from_u32(3)).is_zero());
+        assert!(!Scalar::from_u32(1).is_zero());
+        assert!(!Scalar::generate().is_zero());
+
+        let g = Point::generator();
+        assert!(Point::infinity().is_identity());
+        assert!(g.mul(&Scalar::zero()).is_identity());
+        assert!((g.clone() - g.clone()).is_identity());
+        assert!(!g.is_identity());
+        assert!(!Point::from_scalar(&Scalar::generate()).is_identity());
+    }
+
+    #[test]
+    fn crypto_hash_eq() {
+        use std::collections::{HashMap, HashSet};
+
+        let (public, _) = crypto::create_keypair();
+        // the same point, reached through different projective coordinates
+        let s = Scalar::generate();
+        let other = PublicKey {
+            point: public.point.clone() + Point::from_scalar(&s) - Point::from_scalar(&s),
+        };
+        let decoded = PublicKey::from_bytes(&public.to_bytes()).unwrap();
+        assert!(other == public && decoded == public);
+
+        let mut set = HashSet::new();
+        set.insert(public.clone());
+        set.insert(other);
+        set.insert(decoded);
+        assert_eq!(set.len(), 1);
+
+        let mut points = HashSet::new();
+        points.insert(Point::generator().mul(&Scalar::from_u32(6)));
+        points.insert(&Point::generator().mul(&Scalar::from_u32(2)) * Scalar::from_u32(3));
+        points.insert(Point::infinity());
+        points.insert(Point::generator() - Point::generator());
+        assert_eq!(points.len(), 2);
+
+        let mut map = HashMap::new();
+        map.insert(public.clone(), 1);
+        assert_eq!(map.get(&PublicKey::from_hex(&public.to_hex()).unwrap()), Some(&1));
+    }
+
+    #[test]
+    fn crypto_canonical_serialize() {
+        use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+        let (public, private) = crypto::create_keypair();
+        let mut points: Vec<Point> = (0..10)
+            .map(|_| Point::from_scalar(&Scalar::generate()))
+            .collect();
+        points.push(Point::infinity());
+        for p in points.iter() {
+            assert_eq!(p.serialized_size(), 48);
+            assert_eq!(p.uncompressed_size(), 96);
+            let mut compressed = Vec::new();
+            p.serialize(&mut compressed).unwrap();
+            assert_eq!(compressed, p.to_bytes_compressed());
+            assert!(Point::deserialize(&compressed[..]).unwrap() == *p);
+            let mut uncompressed = Vec::new();
+            p.serialize_uncompressed(&mut uncompressed).unwrap();
+            assert_eq!(uncompressed.len(), 96);
+            assert!(Point::deserialize_uncompressed(&uncompressed[..]).unwrap() == *p);
+        }
+
+        // inside a larger arkworks structure
+        let mut buf = Vec::new();
+        points.serialize(&mut buf).unwrap();
+        assert_eq!(buf.len(), points.serialized_size());
+        assert!(Vec::<Point>::deserialize(&buf[..]).unwrap() == points);
+
+        let mut buf = Vec::new();
+        public.serialize(&mut buf).unwrap();
+        private.serialize(&mut buf).unwrap();
+        private.scalar.serialize_uncompressed(&mut buf).unwrap();
+        assert_eq!(public.serialized_size() + 2 * private.serialized_size(), buf.len());
+        assert_eq!(private.serialized_size(), 32);
+        let mut reader = &buf[..];
+        assert!(PublicKey::deserialize(&mut reader).unwrap() == public);
+        assert!(PrivateKey::deserialize(&mut reader).unwrap() == private);
+        assert!(Scalar::deserialize_uncompressed(&mut reader).unwrap() == private.scalar);
+        assert!(reader.is_empty());
+
+        // off the curve, or truncated
+        let mut uncompressed = Vec::new();
+        points[0].serialize_uncompressed(&mut uncompressed).unwrap();
+        uncompressed[0] ^= 1;
+        assert!(Point::deserialize_uncompressed(&uncompressed[..]).is_err());
+        let mut compressed = Vec::new();
+        points[0].serialize(&mut compressed).unwrap();
+        assert!(Point::deserialize(&compressed[..47]).is_err());
+        assert!(Scalar::deserialize(&[0xff; 32][..]).is_err());
+    }
+
+    #[test]
+    fn crypto_point_g2() {
+        let g = PointG2::generator();
+        assert!(PointG2::from_scalar(&Scalar::multiplicative_identity()) == g);
+        for _ in 0..NB_TESTS {
+            let a = Scalar::generate();
+            let b = Scalar::generate();
+            let p = PointG2::from_scalar(&a);
+            assert!(g.mul(&(a.clone() + b.clone())) == p.clone() + g.mul(&b));
+            assert!(p.clone() - p.clone() == PointG2::infinity());
+            assert!((p.clone() + (-p.clone())).is_identity());
+
+            let bytes = p.to_bytes();
+            assert_eq!(bytes.len(), 193);
+            assert!(PointG2::from_bytes(&bytes).unwrap() == p);
+        }
+        let inf = PointG2::infinity();
+        assert!(PointG2::from_bytes(&inf.to_bytes()).unwrap() == inf);
+
+        let mut bytes = g.to_bytes();
+        assert_eq!(
+            PointG2::from_bytes(&bytes[1..]).err(),
+            Some(DecodeError::InvalidLength)
+        );
+        bytes[0] ^= 1;
+        assert_eq!(
+            PointG2::from_bytes(&bytes).err(),
+            Some(DecodeError::NotOnCurve)
+        );
+        assert!(PointG2::try_from_bytes(&[0xff; 193]).is_err());
+    }
+
+    #[test]
+    fn crypto_g2_hash_to_curve() {
+        let p = PointG2::hash_to_curve_try_and_increment(b"msg", b"DST-A");
+        assert!(p == PointG2::hash_to_curve_try_and_increment(b"msg", b"DST-A"));
+        assert!(p != PointG2::hash_to_curve_try_and_increment(b"msg", b"DST-B"));
+        assert!(p != PointG2::hash_to_curve_try_and_increment(b"msh", b"DST-A"));
+        assert!(!p.is_identity());
+        // decoding checks the subgroup
+        assert!(PointG2::from_bytes(&p.to_bytes()).unwrap() == p);
+    }
+
+    #[test]
+    fn crypto_hash_to_curve() {
+        use ark_bls12_381::{Fq, G1Affine};
+        use ark_ff::{PrimeField, ToBytes};
+
+        // the test vectors of RFC 9380, appendix J.9.1
+        let dst = b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_";
+        let q128 = [&b"q128_"[..], &[b'q'; 128]].concat();
+        let a512 = [&b"a512_"[..], &[b'a'; 512]].concat();
+        let vectors: [(&[u8], &str, &str); 5] = [
+            (
+                b"",
+                "052926add2207b76ca4fa57a8734416c8dc95e24501772c814278700eed6d1e4\
+                 e8cf62d9c09db0fac349612b759e79a1",
+                "08ba738453bfed09cb546dbb0783dbb3a5f1f566ed67bb6be0e8c67e2e81a4cc\
+                 68ee29813bb7994998f3eae0c9c6a265",
+            ),
+            (
+                b"abc",
+                "03567bc5ef9c690c2ab2ecdf6a96ef1c139cc0b2f284dca0a9a7943388a49a3a\
+                 ee664ba5379a7655d3c68900be2f6903",
+                "0b9c15f3fe6e5cf4211f346271d7b01c8f3b28be689c8429c85b67af21553331\
+                 1f0b8dfaaa154fa6b88176c229f2885d",
+            ),
+            (
+                b"abcdef0123456789",
+                "11e0b079dea29a68f0383ee94fed1b940995272407e3bb916bbf268c263ddd57\
+                 a6a27200a784cbc248e84f357ce82d98",
+                "03a87ae2caf14e8ee52e51fa2ed8eefe80f02457004ba4d486d6aa1f517c0889\
+                 501dc7413753f9599b099ebcbbd2d709",
+            ),
+            (
+                &q128,
+                "15f68eaa693b95ccb85215dc65fa81038d69629f70aeee0d0f677cf22285e7bf\
+                 58d7cb86eefe8f2e9bc3f8cb84fac488",
+                "1807a1d50c29f430b8cafc4f8638dfeeadf51211e1602a5f184443076715f91b\
+                 b90a48ba1e370edce6ae1062f5e6dd38",
+            ),
+            (
+                &a512,
+                "082aabae8b7dedb0e78aeb619ad3bfd9277a2f77ba7fad20ef6aabdc6c31d19b\
+                 a5a6d12283553294c1825c4b3ca2dcfe",
+                "05b84ae5a942248eea39e1d91030458c40153f3b654ab7872d779ad1e942856a\
+                 20c438e8d99bc8abfbf74729ce1f7ac8",
+            ),
+        ];
+        for (msg, x, y) in vectors.iter() {
+            let x = Fq::from_be_bytes_mod_order(&hex_bytes(x));
+            let y = Fq::from_be_bytes_mod_order(&hex_bytes(y));
+            let mut bytes = Vec::new();
+            G1Affine::new(x, y, false).write(&mut bytes).unwrap();
+            assert!(Point::hash_to_curve(msg, dst) == Point::from_bytes(&bytes).unwrap());
+        }
+
+        let p = Point::hash_to_curve(b"msg", b"DST-A");
+        assert!(p == Point::hash_to_curve(b"msg", b"DST-A"));
+        assert!(p != Point::hash_to_curve(b"msg", b"DST-B"));
+        assert!(p != Point::hash_to_curve(b"msh", b"DST-A"));
+        assert!(!p.is_identity() && p.is_in_correct_subgroup());
+
+        let h = Point::nums_generator("H");
+        assert!(h == Point::nums_generator("H"));
+        assert!(h != Point::generator() && h != Point::nums_generator("H2"));
+        assert!(!h.is_identity() && h.is_in_correct_subgroup());
+    }
+
+    #[test]
+    fn crypto_point_g2_encodings() {
+        use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+        assert!(crypto::g2_generator() == PointG2::generator());
+        let mut points: Vec<PointG2> = (0..10)
+            .map(|_| PointG2::from_scalar(&Scalar::generate()))
+            .collect();
+        points.push(PointG2::infinity());
+        for q in points.iter() {
+            let compressed = q.to_bytes_compressed();
+            assert_eq!(compressed.len(), 96);
+            assert!(PointG2::from_bytes_compressed(&compressed).unwrap() == *q);
+            assert!(PointG2::from_hex(&q.to_hex()).unwrap() == *q);
+
+            let mut buf = Vec::new();
+            q.serialize(&mut buf).unwrap();
+            assert_eq!(buf, compressed);
+            assert!(PointG2::deserialize(&buf[..]).unwrap() == *q);
+            let mut buf = Vec::new();
+            q.serialize_uncompressed(&mut buf).unwrap();
+            assert!(PointG2::deserialize_uncompressed(&buf[..]).unwrap() == *q);
+        }
+        let compressed = points[0].to_bytes_compressed();
+        assert!(PointG2::from_bytes_compressed(&compressed[1..]).is_err());
+        assert!(PointG2::from_hex("zz").is_err());
+    }
+
+    #[test]
+    fn crypto_pairing_product_eq() {
+        let (p, q) = (Point::generator(), crypto::g2_generator());
+        let a = Scalar::generate();
+        let b = Scalar::generate();
+        let c = Scalar::generate();
+        // e(a.P, b.Q) * e(c.P, Q) == e(P, (ab + c).Q)
+        let lhs = [(p.mul(&a), q.mul(&b)), (p.mul(&c), q.clone())];
+        let rhs = [(p.clone(), q.mul(&(a.clone() * b.clone() + c.clone())))];
+        assert!(crypto::pairing_product_eq(&lhs, &rhs));
+        assert!(crypto::pairing_product_eq(&rhs, &lhs));
+        let wrong = [(p.clone(), q.mul(&(a * b)))];
+        assert!(!crypto::pairing_product_eq(&lhs, &wrong));
+        assert!(crypto::pairing_product_eq(&[], &[]));
+        assert!(crypto::pairing_product_eq(&[(Point::infinity(), q)], &[]));
+    }
+
+    #[test]
+    fn crypto_gt_arithmetic() {
+        let p = Point::generator();
+        let q = PointG2::generator();
+        let e = crypto::pairing(&p, &q);
+        let (a, b, c) = (Scalar::generate(), Scalar::generate(), Scalar::generate());
+        // e(pa, qb) * e(pc, q) == e(p, q)^(ab + c)
+        let product = crypto::pairing(&p.mul(&a), &q.mul(&b)) * crypto::pairing(&p.mul(&c), &q);
+        assert!(product == e.pow(&(a.clone() * b.clone() + c.clone())));
+        assert!(product == e.pow(&a).pow(&b) * e.pow(&c));
+        assert!(product != e.pow(&(a * b)));
+        assert!(e.clone() * Gt::identity() == e);
+
+        let bytes = product.to_bytes();
+        assert_eq!(bytes.len(), 576);
+        assert!(Gt::from_bytes(&bytes).unwrap() == product);
+        assert!(Gt::from_bytes(&Gt::identity().to_bytes()).unwrap() == Gt::identity());
+        assert_eq!(Gt::from_bytes(&bytes[1..]).err(), Some(DecodeError::InvalidLength));
+        // one plus the identity is a field element outside of the subgroup
+        let mut outside = Gt::identity().to_bytes();
+        outside[0] = 2;
+        assert_eq!(Gt::from_bytes(&outside).err(), Some(DecodeError::NotInSubgroup));
+    }
+
+    #[test]
+    fn crypto_pairing_bilinear() {
+        let p = Point::generator();
+        let q = PointG2::generator();
+        let e = crypto::pairing(&p, &q);
+        assert!(e != Gt::identity());
+        assert!(crypto::pairing(&Point::infinity(), &q) == Gt::identity());
+        for _ in 0..10 {
+            let a = Scalar::generate();
+            let b = Scalar::generate();
+            let lhs = crypto::pairing(&p.mul(&a), &q.mul(&b));
+            assert!(lhs == e.pow(&(a.clone() * b.clone())));
+            assert!(lhs == crypto::pairing(&p.mul(&b), &q.mul(&a)));
+            assert!(
+                crypto::pairing(&(p.mul(&a) + p.mul(&b)), &q)
+                    == crypto::pairing(&p.mul(&a), &q) * crypto::pairing(&p.mul(&b), &q)
+            );
+        }
+    }
+
+    #[test]
+    fn crypto_generator_is_standard() {
+        // affine (x, y) of the BLS12-381 G1 generator, big endian
+        let x = "17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+        let y = "08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1";
+        let le_bytes = |hex: &str| {
+            let mut v = hex_bytes(hex);
+            v.reverse();
+            v
+        };
+        let mut expected = le_bytes(x);
+        expected.extend(le_bytes(y));
+        expected.push(0);
+        assert_eq!(Point::generator().to_bytes(), expected);
+
+        // keys still round trip against the new base
+        let (public, private) = crypto::create_keypair();
+        assert!(Point::from_scalar(&private.scalar) == public.point);
+        assert!(PublicKey::from_bytes(&public.to_bytes()).unwrap() == public);
+    }
+
+    #[test]
+    fn crypto_generator_cached() {
+        use ark_ec::ProjectiveCurve;
+
+        let g = Point::generator();
+        let expected = ark_bls12_381::G1Projective::prime_subgroup_generator();
+        assert_eq!(g.to_bytes(), ark_ff::to_bytes![expected.into_affine()].unwrap());
+        let calls: Vec<Point> = (0..1000).map(|_| Point::generator()).collect();
+        assert!(calls.iter().all(|p| *p == g));
+        let handles: Vec<_> = (0..4)
+            .map(|_| std::thread::spawn(|| (Point::generator(), Point::generator_h())))
+            .collect();
+        for h in handles {
+            let (g2, h2) = h.join().unwrap();
+            assert!(g2 == g && h2 == Point::generator_h());
+        }
+        assert!(Point::from_scalar(&Scalar::from_u32(3)) == g.clone() + g.clone() + g);
+    }
+
+    #[test]
+    fn crypto_mul_base_double() {
+        let g = Point::generator();
+        for _ in 0..NB_TESTS {
+            let s = Scalar::generate();
+            assert!(Point::mul_base(&s) == g.mul(&s));
+            let p = g.mul(&s);
+            assert!(p.double() == p.clone() + p.clone());
+            assert!(p.double() == p.mul(&Scalar::from_u32(2)));
+        }
+        assert!(Point::mul_base(&Scalar::zero()).is_identity());
+        assert!(Point::infinity().double().is_identity());
+
+        let poly = math::Polynomial::generate(10);
+        assert!(poly.commit_generator() == poly.commit(&g));
+    }
+
+    #[test]
+    fn crypto_generator_table() {
+        let g = Point::generator();
+        let table = crypto::GeneratorTable::generator();
+        for _ in 0..1000 {
+            let s = Scalar::generate();
+            assert!(table.mul(&s) == g.mul(&s));
+            assert!(Point::from_scalar(&s) == g.mul(&s));
+        }
+        assert!(table.mul(&Scalar::zero()) == Point::infinity());
+        assert!(table.mul(&Scalar::multiplicative_identity()) == g);
+        assert!(table.mul(&-Scalar::multiplicative_identity()) == -g);
+
+        let h = Point::generator_h();
+        let table_h = crypto::GeneratorTable::new(&h);
+        let s = Scalar::generate();
+        assert!(table_h.mul(&s) == h.mul(&s));
+    }
+
+    #[test]
+    fn crypto_generator_h() {
+        let h = Point::generator_h();
+        assert!(h == Point::generator_h());
+        assert!(h != Point::generator());
+        assert!(h != Point::infinity());
+        assert!(h.is_in_correct_subgroup());
+    }
+
+    #[test]
+    fn crypto_decode_rejects_garbage() {
+        let (public, private) = crypto::create_keypair();
+        let pub_bytes = public.to_bytes();
+        let priv_bytes = private.to_bytes();
+        assert!(PublicKey::from_bytes(&pub_bytes).unwrap() == public);
+        assert!(PrivateKey::from_bytes(&priv_bytes).unwrap() == private);
+
+        assert_eq!(
+            PublicKey::from_bytes(&pub_bytes[1..]).err(),
+            Some(DecodeError::InvalidLength)
+        );
+        assert_eq!(
+            PrivateKey::from_bytes(&priv_bytes[1..]).err(),
+            Some(DecodeError::InvalidLength)
+        );
+
+        let mut bad_point = pub_bytes.clone();
+        bad_point[0] ^= 1;
+        assert_eq!(
+            Point::from_bytes(&bad_point).err(),
+            Some(DecodeError::NotOnCurve)
+        );
+        let mut bad_point = pub_bytes.clone();
+        bad_point[..48].copy_from_slice(&[0xff; 48]);
+        assert_eq!(
+            Point::from_bytes(&bad_point).err(),
+            Some(DecodeError::InvalidPoint)
+        );
+        assert_eq!(
+            Scalar::from_bytes(&[0xff; 32]).err(),
+            Some(DecodeError::InvalidScalar)
+        );
+    }
+
+    #[test]
+    fn crypto_try_from_bytes_never_panics() {
+        use super::error::PvssError;
+
+        let (public, private) = crypto::create_keypair();
+        let mut pub_bytes = public.to_bytes();
+        let mut priv_bytes = private.to_bytes();
+        assert!(PublicKey::try_from_bytes(&pub_bytes).unwrap() == public);
+        assert!(PrivateKey::try_from_bytes(&priv_bytes).unwrap() == private);
+
+        // truncated buffers and trailing bytes
+        for len in 0..pub_bytes.len() {
+            assert!(PublicKey::try_from_bytes(&pub_bytes[..len]).is_err());
+            assert!(Point::try_from_bytes(&pub_bytes[..len]).is_err());
+        }
+        for len in 0..priv_bytes.len() {
+            assert!(PrivateKey::try_from_bytes(&priv_bytes[..len]).is_err());
+            assert!(Scalar::try_from_bytes(&priv_bytes[..len]).is_err());
+        }
+        pub_bytes.push(0);
+        priv_bytes.push(0);
+        assert_eq!(
+            PublicKey::try_from_bytes(&pub_bytes).err(),
+            Some(PvssError::InvalidPointEncoding)
+        );
+        assert_eq!(
+            PrivateKey::try_from_bytes(&priv_bytes).err(),
+            Some(PvssError::InvalidScalarEncoding)
+        );
+
+        // random garbage does not decode to a point
+        for _ in 0..NB_TESTS {
+            let garbage: Vec<u8> = (0..97).map(|_| rand::random::<u8>()).collect();
+            assert!(Point::try_from_bytes(&garbage).is_err());
+        }
+        let mut off_curve = public.to_bytes();
+        off_curve[0] ^= 1;
+        assert_eq!(
+            PublicKey::try_from_bytes(&off_curve).err(),
+            Some(PvssError::NotOnCurve)
+        );
+    }
+
+    #[test]
+    fn crypto_decode_rejects_points_off_subgroup() {
+        use super::error::PvssError;
+        use ark_bls12_381::{FrParameters, Fq, G1Affine};
+        use ark_ec::{AffineCurve, ProjectiveCurve};
+        use ark_ff::{FpParameters, ToBytes, Zero};
+        use ark_serialize::CanonicalSerialize;
+
+        // a point on the curve, which is not in the prime order subgroup
+        // with overwhelming probability given the size of the cofactor
+        let mut x = 1u64;
+        let bogus = loop {
+            if let Some(p) = G1Affine::get_point_from_x(Fq::from(x), false) {
+                break p;
+            }
+            x += 1;
+        };
+        assert!(bogus.is_on_curve());
+        assert!(!bogus.is_in_correct_subgroup_assuming_on_curve());
+
+        let mut bytes = Vec::new();
+        bogus.write(&mut bytes).unwrap();
+        assert_eq!(
+            PublicKey::from_bytes(&bytes).err(),
+            Some(DecodeError::NotInSubgroup)
+        );
+
+        // killing the prime order component leaves a torsion point
+        let torsion = bogus.mul(FrParameters::MODULUS).into_affine();
+        assert!(torsion.is_on_curve() && !torsion.is_zero());
+        let mut bytes = Vec::new();
+        torsion.write(&mut bytes).unwrap();
+        assert_eq!(
+            PublicKey::try_from_bytes(&bytes).err(),
+            Some(PvssError::NotInSubgroup)
+        );
+
+        // the compressed encoding reports the same distinction
+        let mut compressed = Vec::new();
+        torsion.serialize(&mut compressed).unwrap();
+        assert_eq!(
+            Point::from_bytes_compressed(&compressed).err(),
+            Some(PvssError::NotInSubgroup)
+        );
+        let mut tampered = Point::generator().to_bytes_compressed();
+        let off_curve = (0..=255u8).any(|b| {
+            tampered[0] = b;
+            Point::from_bytes_compressed(&tampered).err() == Some(PvssError::NotOnCurve)
+        });
+        assert!(off_curve);
+
+        let (public, _) = crypto::create_keypair();
+        assert!(public.point.is_on_curve() && public.point.is_in_correct_subgroup());
+        assert!(Point::infinity().is_on_curve());
+        assert!(PublicKey::try_from_bytes(&public.to_bytes()).unwrap() == public);
+    }
+
+    #[test]
+    fn crypto_expand_message_xmd() {
+        // test vectors of RFC 9380, appendix K.1
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        assert_eq!(
+            crypto::ark::expand_message_xmd(b"", dst, 0x20),
+            hex_bytes("68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235")
+        );
+        assert_eq!(
+            crypto::ark::expand_message_xmd(b"abc", dst, 0x20),
+            hex_bytes("d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615")
+        );
+        assert_eq!(
+            crypto::ark::expand_message_xmd(b"", dst, 0x80),
+            hex_bytes(concat!(
+                "af84c27ccfd45d41914fdff5df25293e221afc53d8ad2ac06d5e3e29485dadbe",
+                "e0d121587713a3e0dd4d5e69e93eb7cd4f5df4cd103e188cf60cb02edc3edf18",
+                "eda8576c412b18ffb658e3dd6ec849469b979d444cf7b26911a08e63cf31f9dc",
+                "c541708d3491184472c2c29bb749d4286b004ceb5ee6b9a7fa5b646c993f0ced"
+            ))
+        );
+    }
+
+    #[test]
+    fn crypto_hash_points_known_answer() {
+        let c = Scalar::hash_points(vec![Point::generator()]);
+        assert_eq!(
+            c.to_bytes(),
+            hex_bytes("3ebe3b370d32a2be7fcaaa950a1168cc9d444b2cd62b4a2d851bfa13bfe7640b")
+        );
+        let other_dst = Scalar::hash_points_with_dst(&[Point::generator()], b"OTHER-DST");
+        assert!(other_dst != c);
+
+        // the points are hashed in order
+        let g = Point::generator();
+        let g2 = Point::from_scalar(&Scalar::from_u32(2));
+        assert_eq!(
+            Scalar::hash_points(vec![g.clone(), g2.clone()]).to_bytes(),
+            hex_bytes("6a65cea8ff0be156b83ce86b7de476308a790cd5861713bc7972c585be63ba2e")
+        );
+        assert_eq!(
+            Scalar::hash_points(vec![g2, g]).to_bytes(),
+            hex_bytes("26fe4fa654fe833e7ec15969410087084e74f77e1c5ffefb57a75f1867896312")
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn crypto_serde_roundtrip() {
+        let (public, private) = crypto::create_keypair();
+        let s = Scalar::generate();
+        let p = Point::from_scalar(&s);
+
+        let json = serde_json::to_string(&public).unwrap();
+        assert_eq!(json, format!("\"{}\"", hex::encode(public.point.to_bytes_compressed())));
+        assert!(serde_json::from_str::<PublicKey>(&json).unwrap() == public);
+        let json = serde_json::to_string(&private).unwrap();
+        assert!(serde_json::from_str::<PrivateKey>(&json).unwrap() == private);
+        let json = serde_json::to_string(&(s.clone(), p.clone())).unwrap();
+        let (s2, p2): (Scalar, Point) = serde_json::from_str(&json).unwrap();
+        assert!(s2 == s && p2 == p);
+
+        let bin = bincode::serialize(&public).unwrap();
+        assert!(bincode::deserialize::<PublicKey>(&bin).unwrap() == public);
+        let bin = bincode::serialize(&private).unwrap();
+        assert!(bincode::deserialize::<PrivateKey>(&bin).unwrap() == private);
+        let bin = bincode::serialize(&(s.clone(), p.clone())).unwrap();
+        let (s2, p2): (Scalar, Point) = bincode::deserialize(&bin).unwrap();
+        assert!(s2 == s && p2 == p);
+
+        // a corrupt point is a deserialization error
+        let mut bin = bincode::serialize(&p).unwrap();
+        let last = bin.len() - 1;
+        bin[last] ^= 1;
+        assert!(bincode::deserialize::<Point>(&bin).is_err());
+        assert!(serde_json::from_str::<Point>("\"00ff\"").is_err());
+        assert!(serde_json::from_str::<Point>("\"not hex\"").is_err());
+
+        let q = PointG2::from_scalar(&s);
+        let json = serde_json::to_string(&q).unwrap();
+        assert_eq!(json, format!("\"{}\"", hex::encode(q.to_bytes_compressed())));
+        assert!(serde_json::from_str::<PointG2>(&json).unwrap() == q);
+        let bin = bincode::serialize(&q).unwrap();
+        assert!(bincode::deserialize::<PointG2>(&bin).unwrap() == q);
+    }
+
+    #[test]
+    fn crypto_debug_redacts_secrets() {
+        let (public, private) = crypto::create_keypair();
+        let key_hex: String = private.to_bytes().iter().map(|b| format!("{:02x}", b)).collect();
+        let canonical_hex = hex::encode(private.scalar.to_bytes_canonical());
+        for debug in [format!("{:?}", private), format!("{:#?}", private)] {
+            assert_eq!(debug, "PrivateKey(<redacted>)");
+            assert!(!debug.contains(&key_hex) && !debug.contains(&canonical_hex));
+        }
+        assert_eq!(format!("{:?}", private.scalar), "Scalar(<redacted>)");
+
+        let point_hex: String = public
+            .point
+            .to_bytes_compressed()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        assert_eq!(format!("{:?}", public), format!("PublicKey({}..)", &point_hex[..16]));
+        assert_eq!(format!("{:?}", public.point), format!("Point({}..)", &point_hex[..16]));
+        assert_eq!(format!("{}", public), public.to_hex());
+        assert!(PublicKey::from_hex(&public.to_string()).unwrap() == public);
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn crypto_zeroize() {
+        use zeroize::{Zeroize, ZeroizeOnDrop};
+
+        fn zeroize_on_drop<T: ZeroizeOnDrop>() {}
+        zeroize_on_drop::<Scalar>();
+        zeroize_on_drop::<PrivateKey>();
+
+        let (_, mut private) = crypto::create_keypair();
+        private.zeroize();
+        assert!(private.scalar == Scalar::from_u32(0));
+
+        // drop a key in place and look at the memory it occupied
+        let (_, private) = crypto::create_keypair();
+        assert!(private.to_bytes().iter().any(|b| *b != 0));
+        let mut slot = std::mem::MaybeUninit::new(private);
+        let bytes = unsafe {
+            std::ptr::drop_in_place(slot.as_mut_ptr());
+            std::slice::from_raw_parts(
+                slot.as_ptr() as *const u8,
+                std::mem::size_of::<PrivateKey>(),
+            )
+        };
+        assert!(bytes.iter().all(|b| *b == 0));
+    }
+
+    fn crypto_generic_works<E: Curve>() {
+        use super::crypto::ark;
+
+        let a = ark::Scalar::<E>::generate();
+        let b = ark::Scalar::<E>::generate();
+        let g = ark::Point::<E>::generator();
+        assert!(g.mul(&(a.clone() + b.clone())) == g.mul(&a) + g.mul(&b));
+        assert!(g.mul(&(a.clone() * b.clone())) == g.mul(&a).mul(&b));
+        assert!(a.clone() * a.inverse() == ark::Scalar::multiplicative_identity());
+        assert!(ark::Point::<E>::generator_h().is_in_correct_subgroup());
+        let q = ark::PointG2::<E>::from_scalar(&a);
+        assert!(ark::PointG2::<E>::from_bytes(&q.to_bytes()).unwrap() == q);
+
+        let (public, private) = ark::create_keypair::<E>();
+        assert!(ark::Point::from_scalar(&private.scalar) == public.point);
+        assert!(ark::PublicKey::<E>::from_bytes(&public.to_bytes()).unwrap() == public);
+        assert!(ark::PrivateKey::<E>::from_bytes(&private.to_bytes()).unwrap() == private);
+        let bytes = public.to_bytes();
+        assert_eq!(
+            ark::Point::<E>::from_bytes(&bytes[1..]).err(),
+            Some(DecodeError::InvalidLength)
+        );
+
+        let points = vec![g.clone(), public.point.clone()];
+        assert!(
+            ark::Scalar::hash_points(points.clone()) == ark::Scalar::hash_points(points.clone())
+        );
+        assert!(
+            ark::Point::multi_scalar_mul(&points, &[a.clone(), b.clone()])
+                == g.mul(&a) + public.point.mul(&b)
+        );
+    }
+
+    #[test]
+    fn crypto_generic_bls12_381() {
+        crypto_generic_works::<Bls12_381>();
+    }
+
+    #[test]
+    fn crypto_generic_bn254() {
+        crypto_generic_works::<ark_bn254::Bn254>();
+    }
+
+    #[test]
+    fn elgamal_works() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        let mut rng = ChaCha20Rng::from_seed([12; 32]);
+        let (pk, sk) = crypto::create_keypair_with(&mut rng);
+        let (_, other_sk) = crypto::create_keypair_with(&mut rng);
+        for _ in 0..NB_TESTS {
+            let msg = Point::from_scalar(&Scalar::generate_with(&mut rng));
+            let ciphertext = elgamal::encrypt(&pk, &msg, &mut rng);
+            assert!(elgamal::decrypt(&sk, &ciphertext) == msg);
+            assert!(elgamal::decrypt(&other_sk, &ciphertext) != msg);
+
+            let again = elgamal::encrypt(&pk, &msg, &mut rng);
+            assert!(again.0 != ciphertext.0 && again.1 != ciphertext.1);
+            assert!(elgamal::decrypt(&sk, &again) == msg);
+        }
+    }
+
+    #[test]
+    fn bls_sign_verify() {
+        let (pk, sk) = crypto::create_keypair();
+        let msg = b"a message";
+        let sig = bls::sign(&sk, msg);
+        assert!(bls::verify(&pk, msg, &sig));
+        assert!(bls::sign(&sk, msg) == sig);
+
+        let decoded = bls::Signature::from_bytes(&sig.to_bytes()).unwrap();
+        assert!(bls::verify(&pk, msg, &decoded));
+        assert!(bls::Signature::from_bytes(&sig.to_bytes()[1..]).is_err());
+    }
 
-extern crate crypto as cp;
+    #[test]
+    fn bls_tampered_message() {
+        let (pk, sk) = crypto::create_keypair();
+        let sig = bls::sign(&sk, b"a message");
+        assert!(!bls::verify(&pk, b"a massage", &sig));
+        assert!(!bls::verify(&pk, b"", &sig));
+        assert!(bls::verify(&pk, b"", &bls::sign(&sk, b"")));
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::crypto;
-    use super::crypto::*;
-    use super::dleq;
-    use super::scrape;
-    use super::simple;
+    #[test]
+    fn bls_wrong_public_key() {
+        let (pk, sk) = crypto::create_keypair();
+        let (other, _) = crypto::create_keypair();
+        let sig = bls::sign(&sk, b"a message");
+        assert!(bls::verify(&pk, b"a message", &sig));
+        assert!(!bls::verify(&other, b"a message", &sig));
+        let identity = PublicKey {
+            point: Point::infinity(),
+        };
+        let forged = bls::Signature::from_bytes(&PointG2::infinity().to_bytes()).unwrap();
+        assert!(!bls::verify(&identity, b"a message", &forged));
+    }
 
-    pub const NB_TESTS: usize = 100;
     #[test]
-    fn crypto_point_add_identity() {
-        for _ in 0..NB_TESTS {
-            let i = Scalar::generate();
-            let p = Point::from_scalar(&i);
-            assert!(p.clone() + Point::infinity() == p);
+    fn elgamal_share_works() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        let mut rng = ChaCha20Rng::from_seed([13; 32]);
+        let (pk, sk) = crypto::create_keypair_with(&mut rng);
+        let (_, other_sk) = crypto::create_keypair_with(&mut rng);
+        let share = Scalar::generate_with(&mut rng);
+        let encrypted = elgamal::encrypt_share(&pk, &share);
+        assert!(encrypted == pk.point.mul(&share));
+        assert!(elgamal::decrypt_share(&sk, &encrypted) == Point::from_scalar(&share));
+        assert!(elgamal::decrypt_share(&other_sk, &encrypted) != Point::from_scalar(&share));
+    }
+
+    #[test]
+    fn elgamal_decrypt_share_with_proof() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        let mut rng = ChaCha20Rng::from_seed([17; 32]);
+        let (pk, sk) = crypto::create_keypair_with(&mut rng);
+        let (other_pk, other_sk) = crypto::create_keypair_with(&mut rng);
+        let msg = Point::from_scalar(&Scalar::generate_with(&mut rng));
+        let ciphertext = elgamal::encrypt(&pk, &msg, &mut rng);
+
+        let (decrypted, proof) = elgamal::decrypt_share_with_proof(&sk, &ciphertext);
+        assert!(decrypted == msg);
+        assert!(elgamal::verify_decrypted_share(&pk, &ciphertext, &decrypted, &proof));
+        assert!(!elgamal::verify_decrypted_share(&other_pk, &ciphertext, &decrypted, &proof));
+
+        // a wrong decryption does not verify, with the honest proof or a
+        // proof made with another key
+        let wrong = decrypted.double();
+        assert!(!elgamal::verify_decrypted_share(&pk, &ciphertext, &wrong, &proof));
+        let (forged_point, forged) = elgamal::decrypt_share_with_proof(&other_sk, &ciphertext);
+        assert!(forged_point != msg);
+        assert!(!elgamal::verify_decrypted_share(&pk, &ciphertext, &forged_point, &forged));
+    }
+
+    #[test]
+    fn elgamal_encryption_context() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        let mut rng = ChaCha20Rng::from_seed([14; 32]);
+        let pks: Vec<PublicKey> = (0..4).map(|_| crypto::create_keypair_with(&mut rng).0).collect();
+        let context = elgamal::EncryptionContext::new(&pks);
+        let small = elgamal::EncryptionContext::with_window(&pks, 3);
+        for _ in 0..2 {
+            let shares: Vec<Scalar> = (0..4).map(|_| Scalar::generate_with(&mut rng)).collect();
+            let naive: Vec<Point> =
+                pks.iter().zip(&shares).map(|(pk, s)| elgamal::encrypt_share(pk, s)).collect();
+            assert!(context.encrypt_shares(&shares) == naive);
+            assert!(small.encrypt_shares(&shares) == naive);
         }
     }
 
     #[test]
-    fn crypto_point_generator() {
+    fn math_polynomial() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        let mut rng = ChaCha20Rng::from_seed([9; 32]);
+        let poly = math::Polynomial::random(4, &mut rng);
+        assert_eq!(poly.degree(), 4);
+        assert_eq!(poly.len(), 5);
+        assert!(poly.eval(&Scalar::zero()) == poly.at_zero());
+
         let g = Point::generator();
+        let commitments = poly.commit(&g);
+        assert_eq!(commitments.len(), poly.len());
         for _ in 0..NB_TESTS {
-            let i = Scalar::generate();
-            let p1 = Point::from_scalar(&i);
-            let p2 = g.mul(&i);
-            assert!(p1 == p2);
+            let x = Scalar::generate_with(&mut rng);
+            let naive: Scalar = poly
+                .elements
+                .iter()
+                .enumerate()
+                .map(|(i, a)| a.clone() * x.pow(i as u32))
+                .sum();
+            let y = poly.eval(&x);
+            assert!(y == naive);
+            assert!(poly.evaluate(x.clone()) == y);
+
+            // g^p(x) from the commitments alone
+            let from_commitments: Point = commitments
+                .iter()
+                .enumerate()
+                .map(|(i, c)| c * &x.pow(i as u32))
+                .sum();
+            assert!(from_commitments == g.mul(&y));
+        }
+    }
+
+    #[test]
+    fn math_polynomial_with_secret() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        let mut rng = ChaCha20Rng::from_seed([10; 32]);
+        let secret = Scalar::generate_with(&mut rng);
+        for degree in 0..5 {
+            let poly = math::Polynomial::random_with_secret(degree, secret.clone(), &mut rng);
+            assert_eq!(poly.degree(), degree as usize);
+            assert_eq!(poly.coefficients().len(), degree as usize + 1);
+            assert!(poly.coefficients()[0] == secret);
+            assert!(poly.eval(&Scalar::zero()) == secret);
+        }
+
+        // 3 + 2x + x^2
+        let poly = math::Polynomial {
+            elements: vec![Scalar::from_u32(3), Scalar::from_u32(2), Scalar::from_u32(1)],
+        };
+        assert!(poly.eval(&Scalar::from_u32(0)) == Scalar::from_u32(3));
+        assert!(poly.eval(&Scalar::from_u32(1)) == Scalar::from_u32(6));
+        assert!(poly.eval(&Scalar::from_u32(4)) == Scalar::from_u32(27));
+        // a constant stays constant
+        let poly = math::Polynomial::random_with_secret(0, secret.clone(), &mut rng);
+        assert!(poly.eval(&Scalar::generate()) == secret);
+    }
+
+    #[test]
+    fn sharing_works() {
+        use super::error::PvssError;
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        let mut rng = ChaCha20Rng::from_seed([5; 32]);
+        let secret = Scalar::generate_with(&mut rng);
+        let (t, n) = (3, 5);
+        let shares = sharing::split_secret(&secret, t, n, &mut rng).unwrap();
+        assert_eq!(shares.len(), n);
+
+        assert!(sharing::reconstruct(&shares[..t], t).unwrap() == secret);
+        assert!(sharing::reconstruct(&shares, t).unwrap() == secret);
+        for subset in [[0, 1, 2], [4, 2, 0], [1, 3, 4], [2, 3, 4]] {
+            let chosen: Vec<(Scalar, Scalar)> =
+                subset.iter().map(|&i| shares[i].clone()).collect();
+            assert!(sharing::reconstruct(&chosen, t).unwrap() == secret);
+        }
+        assert_eq!(
+            sharing::reconstruct(&shares[..t - 1], t).err(),
+            Some(PvssError::NotEnoughShares)
+        );
+        // with one share too few, interpolating at a lower threshold is wrong
+        assert!(sharing::reconstruct(&shares[..t - 1], t - 1).unwrap() != secret);
+
+        assert_eq!(
+            sharing::split_secret(&secret, n + 1, n, &mut rng).err(),
+            Some(PvssError::InvalidThreshold)
+        );
+        assert_eq!(
+            sharing::split_secret(&secret, 0, n, &mut rng).err(),
+            Some(PvssError::InvalidThreshold)
+        );
+    }
+
+    #[test]
+    fn sharing_params() {
+        use super::error::PvssError;
+        use super::sharing::ShareParams;
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        let params = ShareParams::new(3, 5).unwrap();
+        assert_eq!((params.threshold(), params.n(), params.degree()), (3, 5, 2));
+        assert!(ShareParams::new(1, 1).is_ok());
+        assert!(ShareParams::new(5, 5).is_ok());
+        assert_eq!(ShareParams::new(0, 5), Err(PvssError::InvalidThreshold));
+        assert_eq!(ShareParams::new(6, 5), Err(PvssError::InvalidThreshold));
+        assert_eq!(ShareParams::new(0, 0), Err(PvssError::NoParticipants));
+        assert_eq!(ShareParams::new(1, 0), Err(PvssError::NoParticipants));
+
+        let mut rng = ChaCha20Rng::from_seed([6; 32]);
+        let secret = Scalar::generate_with(&mut rng);
+        assert_eq!(
+            sharing::split_secret(&secret, 1, 0, &mut rng).err(),
+            Some(PvssError::NoParticipants)
+        );
+        assert_eq!(
+            pvss::Dealer::deal(&secret, &[], 1, &mut rng).err(),
+            Some(PvssError::NoParticipants)
+        );
+        assert_eq!(PvssError::NoParticipants.to_string(), "no participants");
+    }
+
+    #[test]
+    fn sharing_interpolate_at_zero() {
+        use super::error::PvssError;
+
+        let poly = math::Polynomial::generate(2);
+        let points: Vec<(Scalar, Scalar)> = (1..=6)
+            .map(|i| {
+                let x = Scalar::from_u32(i);
+                let y = poly.eval(&x);
+                (x, y)
+            })
+            .collect();
+        for subset in [&points[..3], &points[3..], &points[1..4], &points[..]] {
+            assert!(sharing::lagrange_interpolate_at_zero(subset).unwrap() == poly.at_zero());
+        }
+        // below the threshold the result is unrelated to the secret
+        assert!(sharing::lagrange_interpolate_at_zero(&points[..2]).unwrap() != poly.at_zero());
+        assert_eq!(
+            sharing::lagrange_interpolate_at_zero(&[]).err(),
+            Some(PvssError::NotEnoughShares)
+        );
+        let duplicated = [points[0].clone(), points[1].clone(), points[0].clone()];
+        assert_eq!(
+            sharing::lagrange_interpolate_at_zero(&duplicated).err(),
+            Some(PvssError::DuplicateIndex)
+        );
+    }
+
+    #[test]
+    fn sharing_non_contiguous_subset() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        let mut rng = ChaCha20Rng::from_seed([13; 32]);
+        let secret = Scalar::generate_with(&mut rng);
+        let shares = sharing::split_secret(&secret, 3, 8, &mut rng).unwrap();
+        for (i, (x, _)) in shares.iter().enumerate() {
+            assert!(*x == Scalar::from_u32(i as u32 + 1));
+        }
+        let subset: Vec<(Scalar, Scalar)> =
+            [2, 5, 7].iter().map(|&i| shares[i - 1].clone()).collect();
+        assert!(sharing::lagrange_interpolate_at_zero(&subset).unwrap() == secret);
+        assert!(sharing::reconstruct(&subset, 3).unwrap() == secret);
+
+        let points: Vec<(Scalar, Point)> = subset
+            .iter()
+            .map(|(x, y)| (x.clone(), Point::from_scalar(y)))
+            .collect();
+        assert!(
+            sharing::lagrange_interpolate_points_at_zero(&points).unwrap()
+                == Point::from_scalar(&secret)
+        );
+        // the shares are not interchangeable with those at other indices
+        let relabeled: Vec<(Scalar, Scalar)> = subset
+            .iter()
+            .enumerate()
+            .map(|(i, (_, y))| (Scalar::from_u32(i as u32 + 1), y.clone()))
+            .collect();
+        assert!(sharing::lagrange_interpolate_at_zero(&relabeled).unwrap() != secret);
+    }
+
+    #[test]
+    fn sharing_interpolate_points_at_zero() {
+        use super::error::PvssError;
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        let mut rng = ChaCha20Rng::from_seed([13; 32]);
+        let secret = Scalar::generate_with(&mut rng);
+        let shares = sharing::split_secret(&secret, 4, 7, &mut rng).unwrap();
+        let point_shares: Vec<(Scalar, Point)> = shares
+            .iter()
+            .map(|(x, y)| (x.clone(), Point::from_scalar(y)))
+            .collect();
+        let expected = Point::from_scalar(&secret);
+
+        // drop three of the seven shares
+        let kept: Vec<(Scalar, Point)> = [6, 1, 4, 2]
+            .iter()
+            .map(|&i| point_shares[i].clone())
+            .collect();
+        assert!(sharing::lagrange_interpolate_points_at_zero(&kept).unwrap() == expected);
+        assert!(sharing::lagrange_interpolate_points_at_zero(&kept[..3]).unwrap() != expected);
+        assert_eq!(
+            sharing::lagrange_interpolate_points_at_zero(&[]).err(),
+            Some(PvssError::NotEnoughShares)
+        );
+        let duplicated = [kept[0].clone(), kept[0].clone()];
+        assert_eq!(
+            sharing::lagrange_interpolate_points_at_zero(&duplicated).err(),
+            Some(PvssError::DuplicateIndex)
+        );
+    }
+
+    #[test]
+    fn sharing_verify_share() {
+        let poly = math::Polynomial::generate(3);
+        let commitments = poly.commit(&Point::generator());
+        for i in 1..=5 {
+            let index = Scalar::from_u32(i);
+            let share = poly.eval(&index);
+            assert!(sharing::verify_share(&index, &share, &commitments));
+
+            let mut bytes = share.to_bytes_canonical();
+            bytes[0] ^= 1;
+            let flipped = Scalar::from_bytes_canonical(&bytes).unwrap();
+            assert!(!sharing::verify_share(&index, &flipped, &commitments));
+            let wrong_index = Scalar::from_u32(i + 1);
+            assert!(!sharing::verify_share(&wrong_index, &share, &commitments));
+
+            // too few or too many commitments
+            assert!(!sharing::verify_share(&index, &share, &commitments[..3]));
+            let mut longer = commitments.clone();
+            longer.push(Point::generator());
+            assert!(!sharing::verify_share(&index, &share, &longer));
+            assert!(!sharing::verify_share(&index, &share, &[]));
+        }
+    }
+
+    #[test]
+    fn sharing_lagrange_coefficients() {
+        use super::error::PvssError;
+
+        let poly = math::Polynomial::generate(3);
+        let indices: Vec<Scalar> = [1, 2, 5, 9].iter().map(|&i| Scalar::from_u32(i)).collect();
+        let values: Vec<Scalar> = indices.iter().map(|x| poly.eval(x)).collect();
+        let targets = [
+            Scalar::zero(),
+            Scalar::from_u32(3),
+            Scalar::from_u32(5),
+            Scalar::generate(),
+        ];
+        for at in targets.iter() {
+            let coefficients = sharing::lagrange_coefficients(&indices, at).unwrap();
+            assert_eq!(coefficients.len(), indices.len());
+            let y: Scalar = coefficients
+                .iter()
+                .zip(values.iter())
+                .map(|(l, v)| l * v)
+                .sum();
+            assert!(y == poly.eval(at));
+        }
+
+        let duplicated = [Scalar::from_u32(1), Scalar::from_u32(4), Scalar::from_u32(1)];
+        assert_eq!(
+            sharing::lagrange_coefficients(&duplicated, &Scalar::zero()).err(),
+            Some(PvssError::DuplicateIndex)
+        );
+        let shares = vec![
+            (Scalar::from_u32(2), Scalar::generate()),
+            (Scalar::from_u32(2), Scalar::generate()),
+        ];
+        assert_eq!(
+            sharing::reconstruct(&shares, 2).err(),
+            Some(PvssError::DuplicateIndex)
+        );
+    }
+
+    #[test]
+    fn sharing_participant_index() {
+        use super::error::PvssError;
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        for i in 1..10 {
+            assert!(sharing::participant_index(i) == Scalar::from_u32(i));
+            assert!(!sharing::participant_index(i).is_zero());
+        }
+        let mut rng = ChaCha20Rng::from_seed([16; 32]);
+        let shares = sharing::split_secret(&Scalar::generate(), 2, 3, &mut rng).unwrap();
+        for (i, (x, _)) in shares.iter().enumerate() {
+            assert!(*x == sharing::participant_index(i as u32 + 1));
+        }
+
+        // index 0 is never a participant
+        assert!(std::panic::catch_unwind(|| sharing::participant_index(0)).is_err());
+        let at_zero = [(Scalar::zero(), Scalar::generate()), shares[0].clone()];
+        assert_eq!(sharing::reconstruct(&at_zero, 2).err(), Some(PvssError::ZeroIndex));
+        let at_zero = [(0, Point::generator()), (1, Point::generator())];
+        assert_eq!(pvss::reconstruct(&at_zero, 2).err(), Some(PvssError::ZeroIndex));
+
+        // indices past u32 are rejected rather than truncated
+        assert_eq!(sharing::try_participant_index(0).err(), Some(PvssError::ZeroIndex));
+        assert!(sharing::try_participant_index(7).unwrap() == sharing::participant_index(7));
+        for i in [1usize << 32, (1 << 32) + 1] {
+            assert_eq!(sharing::try_participant_index(i).err(), Some(PvssError::IndexTooLarge));
+            let wrapped = [(i, Point::generator()), (2, Point::generator())];
+            assert_eq!(pvss::reconstruct(&wrapped, 2).err(), Some(PvssError::IndexTooLarge));
+        }
+    }
+
+    #[test]
+    fn sharing_reconstruct_duplicate_indices() {
+        use super::error::PvssError;
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        let mut rng = ChaCha20Rng::from_seed([9; 32]);
+        let secret = Scalar::generate_with(&mut rng);
+        let shares = sharing::split_secret(&secret, 2, 3, &mut rng).unwrap();
+        let (s1, s2, s3) = (&shares[0].1, &shares[1].1, &shares[2].1);
+        let replayed = [
+            (Scalar::from_u32(1), s1.clone()),
+            (Scalar::from_u32(2), s2.clone()),
+            (Scalar::from_u32(2), s3.clone()),
+        ];
+        // the replayed index is past the threshold but still rejected
+        assert_eq!(
+            sharing::reconstruct(&replayed, 2).err(),
+            Some(PvssError::DuplicateIndex)
+        );
+        assert!(sharing::reconstruct(&shares, 2).unwrap() == secret);
+        assert!(sharing::reconstruct(&shares[1..], 2).unwrap() == secret);
+
+        let decrypted: Vec<(usize, Point)> = shares
+            .iter()
+            .enumerate()
+            .map(|(i, (_, s))| (i + 1, Point::from_scalar(s)))
+            .collect();
+        let mut replayed = decrypted.clone();
+        replayed[2].0 = 2;
+        assert_eq!(pvss::reconstruct(&replayed, 2).err(), Some(PvssError::DuplicateIndex));
+        assert!(pvss::reconstruct(&decrypted, 2).unwrap() == Point::from_scalar(&secret));
+    }
+
+    #[test]
+    fn pedersen_commitments() {
+        let (v1, b1) = (Scalar::generate(), Scalar::generate());
+        let (v2, b2) = (Scalar::generate(), Scalar::generate());
+        let c1 = pedersen::commit(&v1, &b1);
+        let c2 = pedersen::commit(&v2, &b2);
+        assert!(pedersen::verify(&c1, &v1, &b1));
+        assert!(!pedersen::verify(&c1, &v2, &b1));
+        assert!(!pedersen::verify(&c1, &v1, &b2));
+        assert!(!pedersen::verify(&c1, &b1, &v1));
+
+        let sum = pedersen::add(&c1, &c2);
+        assert!(sum == pedersen::commit(&(v1.clone() + v2.clone()), &(b1.clone() + b2.clone())));
+        let opening = pedersen::Opening::new(v1, b1) + pedersen::Opening::new(v2, b2);
+        assert!(pedersen::open(&sum, &opening) && opening.commit() == sum);
+        assert!(!pedersen::open(&c1, &opening));
+    }
+
+    #[test]
+    fn schnorr_proof_of_knowledge() {
+        let (pk, sk) = crypto::create_keypair();
+        let (other_pk, other_sk) = crypto::create_keypair();
+        let proof = SchnorrProof::create(&sk);
+        assert!(proof.verify(&pk));
+        assert!(!proof.verify(&other_pk));
+        assert!(SchnorrProof::create(&other_sk).verify(&other_pk));
+
+        let decoded = SchnorrProof::from_bytes(&proof.to_bytes()).unwrap();
+        assert!(decoded.verify(&pk));
+        let mut bytes = proof.to_bytes();
+        bytes[40] ^= 1;
+        if let Ok(tampered) = SchnorrProof::from_bytes(&bytes) {
+            assert!(!tampered.verify(&pk));
         }
+        assert!(SchnorrProof::from_bytes(&bytes[1..]).is_err());
+    }
+
+    #[test]
+    fn transcript_domain_separation() {
+        let g = Point::generator();
+        let h = Point::generator_h();
+        let s = Scalar::from_u32(7);
+        let challenge = |label: &[u8], first: &Point, second: &Point| {
+            let mut t = Transcript::new(label);
+            t.append_point(b"g", first);
+            t.append_point(b"h", second);
+            t.append_scalar(b"s", &s);
+            t.challenge_scalar(b"c")
+        };
+        let c = challenge(b"dleq", &g, &h);
+        assert!(c == challenge(b"dleq", &g, &h));
+        assert!(c != challenge(b"schnorr", &g, &h));
+
+        // the same points in different label slots give different challenges
+        assert!(c != challenge(b"dleq", &h, &g));
+        let mut relabelled = Transcript::new(b"dleq");
+        relabelled.append_point(b"h", &g);
+        relabelled.append_point(b"g", &h);
+        relabelled.append_scalar(b"s", &s);
+        assert!(relabelled.challenge_scalar(b"c") != c);
+        let mut relabelled = Transcript::new(b"dleq");
+        relabelled.append_point(b"g", &g);
+        relabelled.append_point(b"h", &h);
+        relabelled.append_scalar(b"t", &s);
+        assert!(relabelled.challenge_scalar(b"c") != c);
+
+        // absorbing more changes the challenge, and so does asking again
+        let mut t = Transcript::new(b"dleq");
+        let before = t.clone().challenge_scalar(b"c");
+        t.append_point(b"g", &g);
+        let after = t.challenge_scalar(b"c");
+        assert!(after != before && t.challenge_scalar(b"c") != after);
     }
 
     #[test]
     fn dleq_works() {
         for _ in 0..NB_TESTS {
             let a = Scalar::generate();
-            let w = Scalar::generate();
             let extra_gen = Point::from_scalar(&Scalar::generate());
 
             let lifted_a = Point::from_scalar(&a);
             let lifted_extra_a = extra_gen.mul(&a);
 
-            let dleq = dleq::DLEQ {
-                g1: Point::generator(),
-                h1: lifted_a,
-                g2: extra_gen,
-                h2: lifted_extra_a,
-            };
-            let proof = dleq::Proof::create(w, a, dleq.clone());
-            assert!(proof.verify(dleq));
+            let g = Point::generator();
+            let proof = dleq::DleqProof::create(&a, &g, &lifted_a, &extra_gen, &lifted_extra_a);
+            assert!(proof.verify(&g, &lifted_a, &extra_gen, &lifted_extra_a));
+
+            // swapped or mismatched statements must not verify
+            assert!(!proof.verify(&extra_gen, &lifted_extra_a, &g, &lifted_a));
+            let other = extra_gen.mul(&Scalar::generate());
+            assert!(!proof.verify(&g, &lifted_a, &extra_gen, &other));
+            let wrong = dleq::DleqProof::create(&a, &g, &lifted_a, &extra_gen, &other);
+            assert!(!wrong.verify(&g, &lifted_a, &extra_gen, &other));
+        }
+    }
+
+    #[test]
+    fn dleq_prove_verify() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        let mut rng = ChaCha20Rng::from_seed([11; 32]);
+        let g = Point::generator();
+        let h = Point::from_scalar(&Scalar::generate_with(&mut rng));
+        let x = Scalar::generate_with(&mut rng);
+        let (gx, hx) = (g.mul(&x), h.mul(&x));
+
+        let proof = dleq::prove(&g, &h, &x, &mut rng);
+        assert!(dleq::verify(&proof, &g, &h, &gx, &hx));
+        let bytes = proof.to_bytes();
+        assert_eq!(bytes.len(), 128);
+        let decoded = dleq::DleqProof::from_bytes(&bytes).unwrap();
+        assert!(dleq::verify(&decoded, &g, &h, &gx, &hx));
+
+        // tampered commitments or response
+        let mut swapped = bytes.clone();
+        swapped[..48].copy_from_slice(&bytes[48..96]);
+        swapped[48..96].copy_from_slice(&bytes[..48]);
+        let proof = dleq::DleqProof::from_bytes(&swapped).unwrap();
+        assert!(!dleq::verify(&proof, &g, &h, &gx, &hx));
+        let mut tampered = bytes.clone();
+        tampered[96] ^= 1;
+        let proof = dleq::DleqProof::from_bytes(&tampered).unwrap();
+        assert!(!dleq::verify(&proof, &g, &h, &gx, &hx));
+        assert!(dleq::DleqProof::from_bytes(&bytes[1..]).is_err());
+
+        // different exponents
+        let y = Scalar::generate_with(&mut rng);
+        assert!(!dleq::verify(&proof, &g, &h, &gx, &h.mul(&y)));
+        let dishonest = dleq::prove(&g, &h, &y, &mut rng);
+        assert!(!dleq::verify(&dishonest, &g, &h, &gx, &hx));
+    }
+
+    #[test]
+    fn dleq_batch_verify() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        let mut rng = ChaCha20Rng::from_seed([14; 32]);
+        let g = Point::generator();
+        let mut proofs = Vec::new();
+        let mut instances = Vec::new();
+        for _ in 0..50 {
+            let h = Point::from_scalar(&Scalar::generate_with(&mut rng));
+            let x = Scalar::generate_with(&mut rng);
+            proofs.push(dleq::prove(&g, &h, &x, &mut rng));
+            instances.push((g.clone(), g.mul(&x), h.clone(), h.mul(&x)));
+        }
+        assert!(dleq::batch_verify(&proofs, &instances, &mut rng));
+        assert!(!dleq::batch_verify(&proofs[1..], &instances, &mut rng));
+        assert!(dleq::batch_verify(&[], &[], &mut rng));
+
+        // one flipped response makes the whole batch fail
+        let mut bytes = proofs[17].to_bytes();
+        bytes[100] ^= 1;
+        proofs[17] = dleq::DleqProof::from_bytes(&bytes).unwrap();
+        assert!(!dleq::batch_verify(&proofs, &instances, &mut rng));
+    }
+
+    #[test]
+    fn dleq_batch_verify_mixed() {
+        let mut rng = rand::thread_rng();
+        let g = Point::generator();
+        let mut proofs = Vec::new();
+        let mut statements = Vec::new();
+        for _ in 0..10 {
+            let h = Point::from_scalar(&Scalar::generate());
+            let x = Scalar::generate();
+            let (gx, hx) = (g.mul(&x), h.mul(&x));
+            proofs.push(dleq::DleqProof::create(&x, &g, &gx, &h, &hx));
+            statements.push((g.clone(), gx, h, hx));
         }
+        assert!(dleq::batch_verify(&proofs, &statements, &mut rng));
+
+        // one statement whose two logarithms differ
+        let (g1, h1, g2, _) = statements[4].clone();
+        let h2 = g2.mul(&Scalar::generate());
+        let mut mixed = statements.clone();
+        mixed[4] = (g1.clone(), h1.clone(), g2.clone(), h2.clone());
+        let mut mixed_proofs = proofs.clone();
+        mixed_proofs[4] = dleq::DleqProof::create(&Scalar::generate(), &g1, &h1, &g2, &h2);
+        assert!(!dleq::batch_verify(&mixed_proofs, &mixed, &mut rng));
+        assert!(!dleq::batch_verify(&proofs, &mixed, &mut rng));
+        assert!(dleq::batch_verify_dleq(&proofs, &statements));
+        assert!(!dleq::batch_verify_dleq(&mixed_proofs, &mixed));
+
+        // valid proofs for the wrong statements
+        mixed_proofs.swap(0, 1);
+        assert!(!dleq::batch_verify(&mixed_proofs, &statements, &mut rng));
     }
 
     #[test]
@@ -123,6 +2151,516 @@ mod tests {
         }
     }
 
+    #[test]
+    fn pvss_transcript_round_trip() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        let mut rng = ChaCha20Rng::from_seed([14; 32]);
+        let keys: Vec<(PublicKey, PrivateKey)> = (0..7)
+            .map(|_| crypto::create_keypair_with(&mut rng))
+            .collect();
+        let pks: Vec<PublicKey> = keys.iter().map(|(pk, _)| pk.clone()).collect();
+        let secret = Scalar::generate_with(&mut rng);
+
+        let transcript = pvss::Dealer::deal(&secret, &pks, 4, &mut rng).unwrap();
+        assert!(transcript.verify(&pks));
+
+        let decrypted: Vec<(usize, Point)> = [5, 0, 3, 6]
+            .iter()
+            .map(|&i| {
+                let share = &transcript.shares[i];
+                (share.index, pvss::decrypt_share(&keys[i].1, share))
+            })
+            .collect();
+        let recovered = pvss::reconstruct(&decrypted, 4).unwrap();
+        assert!(recovered == Point::from_scalar(&secret));
+        assert!(pvss::reconstruct(&decrypted[..3], 4).is_err());
+
+        // a share that does not match the commitments is caught
+        let mut bad = transcript.clone();
+        bad.shares[2].value = bad.shares[2].value.clone() + Point::generator();
+        assert!(!bad.verify(&pks));
+        let mut swapped = pks.clone();
+        swapped.swap(0, 1);
+        assert!(!transcript.verify(&swapped));
+        assert!(!transcript.verify(&pks[..6]));
+        assert!(pvss::Dealer::deal(&secret, &pks, 8, &mut rng).is_err());
+    }
+
+    #[test]
+    fn pvss_reshare() {
+        use super::error::PvssError;
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        let mut rng = ChaCha20Rng::from_seed([12; 32]);
+        let g = Point::generator();
+        let secret = Scalar::generate_with(&mut rng);
+        let (t, n) = (3, 5);
+        let poly = math::Polynomial::random_with_secret((t - 1) as u32, secret.clone(), &mut rng);
+        let old_commitments = poly.commit(&g);
+        let old_share = |i: usize| poly.eval(&Scalar::from_u32(i as u32));
+
+        let keys: Vec<(PublicKey, PrivateKey)> =
+            (0..n).map(|_| crypto::create_keypair_with(&mut rng)).collect();
+        let pks: Vec<PublicKey> = keys.iter().map(|(pk, _)| pk.clone()).collect();
+
+        // the old holders 1, 3 and 5 check their Feldman shares and reshare
+        // them to the new committee
+        let holders = [1usize, 3, 5];
+        for &i in holders.iter() {
+            let x = sharing::participant_index(i as u32);
+            assert!(sharing::verify_share(&x, &old_share(i), &old_commitments));
+        }
+        let reshares: Vec<pvss::ReshareTranscript> = holders
+            .iter()
+            .map(|&i| pvss::reshare(i, &old_share(i), &pks, t, &mut rng).unwrap())
+            .collect();
+        for (&i, reshare) in holders.iter().zip(reshares.iter()) {
+            assert_eq!(reshare.index, i);
+            assert!(pvss::verify_reshare(reshare, &old_commitments, &pks));
+            // a reshare claiming the index of another holder
+            let mut wrong = reshare.clone();
+            wrong.index = i + 1;
+            assert!(!pvss::verify_reshare(&wrong, &old_commitments, &pks));
+            wrong.index = 0;
+            assert!(!pvss::verify_reshare(&wrong, &old_commitments, &pks));
+        }
+        assert_eq!(
+            pvss::reshare(0, &old_share(1), &pks, t, &mut rng).err(),
+            Some(PvssError::ZeroIndex)
+        );
+
+        let refreshed: Vec<(usize, Point)> = keys
+            .iter()
+            .enumerate()
+            .map(|(j, (_, sk))| {
+                let sub_shares: Vec<(usize, Point)> = reshares
+                    .iter()
+                    .map(|r| (r.index, pvss::decrypt_share(sk, &r.dealing.shares[j])))
+                    .collect();
+                (j + 1, pvss::combine_reshares(&sub_shares, t).unwrap())
+            })
+            .collect();
+        let expected = g.mul(&secret);
+        assert!(pvss::reconstruct(&refreshed[..t], t).unwrap() == expected);
+        assert!(pvss::reconstruct(&refreshed[2..], t).unwrap() == expected);
+        // the refresh changed every share
+        for (j, share) in refreshed.iter() {
+            assert!(*share != g.mul(&old_share(*j)));
+        }
+        // old and new shares do not mix
+        let mixed = vec![
+            (1, g.mul(&old_share(1))),
+            refreshed[1].clone(),
+            refreshed[2].clone(),
+        ];
+        assert!(pvss::reconstruct(&mixed, t).unwrap() != expected);
+        // fewer sub-shares than the old threshold do not give a share
+        let (_, sk) = &keys[0];
+        let partial: Vec<(usize, Point)> = reshares[..2]
+            .iter()
+            .map(|r| (r.index, pvss::decrypt_share(sk, &r.dealing.shares[0])))
+            .collect();
+        assert_eq!(
+            pvss::combine_reshares(&partial, t).err(),
+            Some(PvssError::NotEnoughShares)
+        );
+        let repeated = vec![partial[0].clone(), partial[0].clone(), partial[1].clone()];
+        assert_eq!(
+            pvss::combine_reshares(&repeated, t).err(),
+            Some(PvssError::DuplicateIndex)
+        );
+    }
+
+    #[test]
+    fn pvss_public_commitment() {
+        use super::error::PvssError;
+
+        let (pks, _): (Vec<PublicKey>, Vec<PrivateKey>) =
+            (0..4).map(|_| crypto::create_keypair()).unzip();
+        let secret = Scalar::from_u32(42);
+        let dealing = pvss::deal(&secret, &pks, 3).unwrap();
+        assert!(dealing.public_commitment().unwrap() == Point::generator().mul(&secret));
+        let mut empty = dealing.clone();
+        empty.commitments.clear();
+        assert_eq!(empty.public_commitment().err(), Some(PvssError::InvalidDealing));
+        // the value at 0 is the secret, not a participant's share
+        assert!(std::panic::catch_unwind(|| dealing.share_commitment(0)).is_err());
+    }
+
+    #[test]
+    fn pvss_verify_share() {
+        use super::error::VerifyError;
+
+        let (pks, _): (Vec<PublicKey>, Vec<PrivateKey>) =
+            (0..5).map(|_| crypto::create_keypair()).unzip();
+        let mut dealing = pvss::deal(&Scalar::generate(), &pks, 3).unwrap();
+        for index in 1..=5 {
+            assert_eq!(dealing.verify_share(index, &pks), Ok(()));
+        }
+        assert_eq!(
+            dealing.verify_share(0, &pks),
+            Err(VerifyError::IndexOutOfRange { index: 0 })
+        );
+        assert_eq!(
+            dealing.verify_share(6, &pks),
+            Err(VerifyError::IndexOutOfRange { index: 6 })
+        );
+        assert_eq!(
+            dealing.verify_share(5, &pks[..4]),
+            Err(VerifyError::IndexOutOfRange { index: 5 })
+        );
+
+        // corrupt one share: only that one is pinpointed
+        dealing.shares[3].value = dealing.shares[3].value.double();
+        assert!(!dealing.verify(&pks));
+        let failures: Vec<_> =
+            (1..=5).filter_map(|i| dealing.verify_share(i, &pks).err()).collect();
+        assert_eq!(failures, vec![VerifyError::InvalidProof { index: 4 }]);
+
+        dealing.commitments.pop();
+        assert_eq!(dealing.verify_share(1, &pks), Err(VerifyError::InvalidDealing));
+    }
+
+    #[test]
+    fn pvss_complaints() {
+        let keys: Vec<(PublicKey, PrivateKey)> = (0..4).map(|_| crypto::create_keypair()).collect();
+        let pks: Vec<PublicKey> = keys.iter().map(|(pk, _)| pk.clone()).collect();
+        let honest = pvss::deal(&Scalar::generate(), &pks, 3).unwrap();
+        for (i, (_, sk)) in keys.iter().enumerate() {
+            assert!(pvss::file_complaint(sk, &honest, i + 1).is_none());
+        }
+        assert!(pvss::file_complaint(&keys[0].1, &honest, 0).is_none());
+        assert!(pvss::file_complaint(&keys[0].1, &honest, 5).is_none());
+
+        // the dealer encrypts a share that is not on the polynomial
+        let mut cheating = honest.clone();
+        cheating.shares[1].value = elgamal::encrypt_share(&pks[1], &Scalar::generate());
+        let complaint = pvss::file_complaint(&keys[1].1, &cheating, 2).unwrap();
+        assert!(pvss::verify_complaint(&cheating, &complaint, &pks[1]));
+        assert!(!pvss::verify_complaint(&cheating, &complaint, &pks[2]));
+        assert!(!pvss::verify_complaint(&honest, &complaint, &pks[1]));
+
+        // a participant cannot frame an honest dealer
+        let (decrypted, proof) = pvss::prove_decryption(&keys[1].1, &honest.shares[1].value);
+        let truthful = pvss::Complaint {
+            index: 2,
+            decrypted: decrypted.clone(),
+            proof: proof.clone(),
+        };
+        assert!(!pvss::verify_complaint(&honest, &truthful, &pks[1]));
+        let forged = pvss::Complaint {
+            index: 2,
+            decrypted: decrypted.double(),
+            proof,
+        };
+        assert!(!pvss::verify_complaint(&honest, &forged, &pks[1]));
+    }
+
+    #[test]
+    fn pvss_dealing_bytes() {
+        use super::error::PvssError;
+
+        let (pks, _): (Vec<PublicKey>, Vec<PrivateKey>) =
+            (0..5).map(|_| crypto::create_keypair()).unzip();
+        let dealing = pvss::deal(&Scalar::generate(), &pks, 3).unwrap();
+        let bytes = dealing.to_bytes();
+        assert_eq!(bytes.len(), 8 + 3 * 48 + 5 * (48 + 128));
+        let decoded = pvss::Dealing::from_bytes(&bytes).unwrap();
+        assert!(decoded.verify(&pks));
+        assert!(decoded.commitments == dealing.commitments);
+        assert_eq!(decoded.to_bytes(), bytes);
+
+        // truncated and extended buffers error rather than panic
+        for len in 0..bytes.len() {
+            assert!(pvss::Dealing::from_bytes(&bytes[..len]).is_err());
+        }
+        let mut longer = bytes.clone();
+        longer.push(0);
+        assert_eq!(pvss::Dealing::from_bytes(&longer).err(), Some(PvssError::InvalidDealing));
+        // headers that are not valid share parameters
+        let header = |threshold: u32, n: u32| {
+            let mut header = threshold.to_le_bytes().to_vec();
+            header.extend_from_slice(&n.to_le_bytes());
+            header
+        };
+        let threshold_zero = [header(0, 5), bytes[8 + 3 * 48..].to_vec()].concat();
+        let no_participants = header(0, 0);
+        let above_n = [header(6, 5), bytes[8..].to_vec(), bytes[8..8 + 3 * 48].to_vec()].concat();
+        let invalid = [
+            (threshold_zero, PvssError::InvalidThreshold),
+            (no_participants, PvssError::NoParticipants),
+            (above_n, PvssError::InvalidThreshold),
+        ];
+        for (header, error) in invalid.iter() {
+            assert_eq!(pvss::Dealing::from_bytes(header).err(), Some(*error));
+        }
+        // huge counts are rejected before allocating
+        let mut huge = bytes.clone();
+        huge[4..8].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(pvss::Dealing::from_bytes(&huge).err(), Some(PvssError::InvalidDealing));
+        for _ in 0..NB_TESTS {
+            let mut garbage = bytes.clone();
+            let i = 8 + rand::random::<usize>() % (bytes.len() - 8);
+            garbage[i] ^= 1 + rand::random::<u8>() % 255;
+            if let Ok(d) = pvss::Dealing::from_bytes(&garbage) {
+                assert!(!d.verify(&pks));
+            }
+        }
+    }
+
+    #[test]
+    fn pvss_aggregate_dealings() {
+        use super::error::{PvssError, VerifyError};
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        let mut rng = ChaCha20Rng::from_seed([15; 32]);
+        let (t, n) = (3, 5);
+        let keys: Vec<(PublicKey, PrivateKey)> =
+            (0..n).map(|_| crypto::create_keypair_with(&mut rng)).collect();
+        let pks: Vec<PublicKey> = keys.iter().map(|(pk, _)| pk.clone()).collect();
+        let secrets: Vec<Scalar> = (0..3).map(|_| Scalar::generate_with(&mut rng)).collect();
+        let dealings: Vec<pvss::Dealing> = secrets
+            .iter()
+            .map(|s| pvss::Dealer::deal(s, &pks, t, &mut rng).unwrap())
+            .collect();
+
+        let aggregate = pvss::Dealing::aggregate(&dealings, &pks).unwrap();
+        let group_key: Point = dealings.iter().map(|d| d.public_commitment().unwrap()).sum();
+        assert!(aggregate.public_commitment().unwrap() == group_key);
+        assert!(aggregate.verify(&pks));
+        let mut bad = aggregate.clone();
+        bad.shares[1] = bad.shares[1].clone() + Point::generator();
+        assert!(!bad.verify(&pks));
+        let mut bad = aggregate.clone();
+        bad.dealings.pop();
+        assert!(!bad.verify(&pks));
+        bad.dealings.clear();
+        assert!(!bad.verify(&pks));
+        let mut bad = aggregate.clone();
+        bad.commitments.clear();
+        assert!(!bad.verify(&pks));
+        assert_eq!(bad.public_commitment().err(), Some(PvssError::InvalidDealing));
+        assert_eq!(aggregate.commitments.len(), t);
+        assert_eq!(aggregate.shares.len(), n);
+        let sum: Scalar = secrets.iter().sum();
+        assert!(aggregate.commitments[0] == Point::from_scalar(&sum));
+        let decrypted: Vec<(usize, Point)> = keys
+            .iter()
+            .enumerate()
+            .map(|(i, (_, sk))| (i + 1, aggregate.decrypt_share(sk, i + 1).unwrap()))
+            .collect();
+        for (index, share) in decrypted.iter() {
+            assert!(*share == aggregate.share_commitment(*index));
+        }
+        for index in [0, n + 1].iter().cloned() {
+            assert_eq!(
+                aggregate.decrypt_share(&keys[0].1, index).err(),
+                Some(VerifyError::IndexOutOfRange { index })
+            );
+        }
+        assert!(pvss::reconstruct(&decrypted[2..], t).unwrap() == Point::from_scalar(&sum));
+
+        // dealings to other participants, with other thresholds, or invalid
+        let other: Vec<PublicKey> = (0..n).map(|_| crypto::create_keypair().0).collect();
+        let foreign = pvss::Dealer::deal(&secrets[0], &other, t, &mut rng).unwrap();
+        let mixed = vec![dealings[0].clone(), foreign];
+        assert_eq!(
+            pvss::Dealing::aggregate(&mixed, &pks).err(),
+            Some(PvssError::InvalidDealing)
+        );
+        let lower = pvss::Dealer::deal(&secrets[0], &pks, t - 1, &mut rng).unwrap();
+        let mixed = vec![dealings[0].clone(), lower];
+        assert_eq!(
+            pvss::Dealing::aggregate(&mixed, &pks).err(),
+            Some(PvssError::InvalidThreshold)
+        );
+        assert_eq!(
+            pvss::Dealing::aggregate(&[], &pks).err(),
+            Some(PvssError::InvalidDealing)
+        );
+    }
+
+    // without `rayon` both paths are the same serial code
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn pvss_deal_parallel_matches_serial() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        fn encode(dealing: &pvss::Dealing) -> Vec<u8> {
+            let mut out = crypto::encode_points(&dealing.commitments);
+            for share in dealing.shares.iter() {
+                out.extend_from_slice(&(share.index as u32).to_le_bytes());
+                out.extend_from_slice(&share.value.to_bytes());
+                out.extend_from_slice(&share.proof.to_bytes());
+            }
+            out
+        }
+
+        let mut rng = ChaCha20Rng::from_seed([15; 32]);
+        let pks: Vec<PublicKey> = (0..256)
+            .map(|_| crypto::create_keypair_with(&mut rng).0)
+            .collect();
+        let secret = Scalar::generate_with(&mut rng);
+        let deal = |parallel| {
+            let mut rng = ChaCha20Rng::from_seed([16; 32]);
+            pvss::Dealer::deal_with(&secret, &pks, 100, &mut rng, parallel).unwrap()
+        };
+        let serial = deal(false);
+        let parallel = deal(true);
+        assert_eq!(encode(&serial), encode(&parallel));
+        assert!(parallel.verify(&pks));
+
+        let mut rng = ChaCha20Rng::from_seed([16; 32]);
+        let default = pvss::Dealer::deal(&secret, &pks, 100, &mut rng).unwrap();
+        assert_eq!(encode(&default), encode(&serial));
+    }
+
+    #[test]
+    fn pvss_deal_end_to_end() {
+        let keys: Vec<(PublicKey, PrivateKey)> = (0..5).map(|_| crypto::create_keypair()).collect();
+        let pks: Vec<PublicKey> = keys.iter().map(|(pk, _)| pk.clone()).collect();
+        let secret = Scalar::generate();
+
+        let dealing: pvss::Dealing = pvss::deal(&secret, &pks, 3).unwrap();
+        assert!(dealing.verify(&pks));
+        assert_eq!(dealing.commitments.len(), 3);
+        assert!(dealing.commitments[0] == Point::from_scalar(&secret));
+
+        // every part of the dealing goes through the byte encodings
+        for c in dealing.commitments.iter() {
+            assert!(Point::from_bytes(&c.to_bytes()).unwrap() == *c);
+        }
+        let shares: Vec<pvss::EncryptedShare> = dealing
+            .shares
+            .iter()
+            .map(|s| pvss::EncryptedShare {
+                index: s.index,
+                value: Point::from_bytes(&s.value.to_bytes()).unwrap(),
+                proof: dleq::DleqProof::from_bytes(&s.proof.to_bytes()).unwrap(),
+            })
+            .collect();
+        let decoded = pvss::Dealing {
+            threshold: dealing.threshold,
+            commitments: dealing.commitments.clone(),
+            shares,
+        };
+        assert!(decoded.verify(&pks));
+
+        let decrypted: Vec<(usize, Point)> = [4, 1, 2]
+            .iter()
+            .map(|&i| {
+                let share = &decoded.shares[i];
+                (share.index, pvss::decrypt_share(&keys[i].1, share))
+            })
+            .collect();
+        assert!(pvss::reconstruct(&decrypted, 3).unwrap() == Point::from_scalar(&secret));
+    }
+
+    #[test]
+    fn pvss_dealing_verify_tampering() {
+        let pks: Vec<PublicKey> = (0..5).map(|_| crypto::create_keypair().0).collect();
+        let dealing = pvss::deal(&Scalar::generate(), &pks, 3).unwrap();
+        assert!(dealing.verify(&pks));
+
+        let mut bad = dealing.clone();
+        let mut proof = bad.shares[3].proof.to_bytes();
+        proof[100] ^= 1;
+        bad.shares[3].proof = dleq::DleqProof::from_bytes(&proof).unwrap();
+        assert!(!bad.verify(&pks));
+
+        let mut bad = dealing.clone();
+        bad.shares[0].proof = dealing.shares[1].proof.clone();
+        assert!(!bad.verify(&pks));
+
+        for j in 0..3 {
+            let mut bad = dealing.clone();
+            bad.commitments[j] = bad.commitments[j].clone() + Point::generator();
+            assert!(!bad.verify(&pks));
+        }
+        let mut bad = dealing.clone();
+        bad.commitments.pop();
+        assert!(!bad.verify(&pks));
+
+        let mut bad = dealing.clone();
+        bad.shares.pop();
+        assert!(!bad.verify(&pks));
+        // still a valid dealing for the first four participants only
+        assert!(bad.verify(&pks[..4]));
+        assert!(!bad.verify(&pks[1..]));
+
+        let mut bad = dealing.clone();
+        bad.shares[1].index = 3;
+        assert!(!bad.verify(&pks));
+    }
+
+    #[test]
+    fn pvss_deal_invalid_threshold() {
+        use super::error::PvssError;
+
+        let pks: Vec<PublicKey> = (0..2).map(|_| crypto::create_keypair().0).collect();
+        for threshold in [0, 3].iter() {
+            assert_eq!(
+                pvss::deal(&Scalar::generate(), &pks, *threshold).err(),
+                Some(PvssError::InvalidThreshold)
+            );
+        }
+    }
+
+    #[test]
+    fn pvss_prove_decryption() {
+        let keys: Vec<(PublicKey, PrivateKey)> = (0..3).map(|_| crypto::create_keypair()).collect();
+        let pks: Vec<PublicKey> = keys.iter().map(|(pk, _)| pk.clone()).collect();
+        let secret = Scalar::generate();
+        let dealing = pvss::deal(&secret, &pks, 2).unwrap();
+
+        let mut decrypted = Vec::new();
+        for (share, (pk, sk)) in dealing.shares.iter().zip(keys.iter()) {
+            let (s, proof) = pvss::prove_decryption(sk, &share.value);
+            assert!(s == pvss::decrypt_share(sk, share));
+            assert!(pvss::verify_decryption(pk, &share.value, &s, &proof));
+
+            // a bogus share, or a proof by the wrong key, is rejected
+            let bogus = s.clone() + Point::generator();
+            assert!(!pvss::verify_decryption(pk, &share.value, &bogus, &proof));
+            let (other, other_proof) = pvss::prove_decryption(&keys[0].1, &share.value);
+            if share.index != 1 {
+                assert!(!pvss::verify_decryption(pk, &share.value, &other, &other_proof));
+            }
+            decrypted.push((share.index, s));
+        }
+        assert!(pvss::reconstruct(&decrypted, 2).unwrap() == Point::from_scalar(&secret));
+    }
+
+    #[test]
+    fn scrape_check_degree() {
+        for &(t, n) in [(1, 4), (3, 7), (5, 20), (6, 6)].iter() {
+            let poly = math::Polynomial::generate(t as u32 - 1);
+            let g = Point::generator();
+            let commitments: Vec<Point> = (1..=n)
+                .map(|i| g.mul(&poly.evaluate(Scalar::from_u32(i))))
+                .collect();
+            assert!(scrape::scrape_check(&commitments, t));
+            assert!(!scrape::scrape_check(&commitments, 0));
+            assert!(!scrape::scrape_check(&commitments, n as usize + 1));
+            if t < n as usize {
+                let mut perturbed = commitments.clone();
+                perturbed[n as usize / 2] = perturbed[n as usize / 2].clone() + g.clone();
+                assert!(!scrape::scrape_check(&perturbed, t));
+                // a polynomial of degree t is caught as well
+                let higher = math::Polynomial::generate(t as u32);
+                let commitments: Vec<Point> = (1..=n)
+                    .map(|i| g.mul(&higher.evaluate(Scalar::from_u32(i))))
+                    .collect();
+                assert!(!scrape::scrape_check(&commitments, t));
+            }
+        }
+    }
+
     #[test]
     fn scrape_works() {
         let tests = [(1, 4), (2, 8), (10, 50), (48, 50), (2, 20), (10, 100)];
@@ -152,8 +2690,8 @@ mod tests {
             for share in &public_shares.encrypted_shares {
                 assert!(share.id > 0);
                 let idx = (share.id - 1) as usize;
-                let d = scrape::decrypt_share(&keys[idx], &pubs[idx], &share);
-                let verified_decrypted = d.verify(&pubs[idx], &share);
+                let d = scrape::decrypt_share(&keys[idx], &pubs[idx], share);
+                let verified_decrypted = d.verify(&pubs[idx], share);
                 assert!(verified_decrypted);
                 decrypted.push(d);
             }
@@ -165,4 +2703,61 @@ mod tests {
             assert!(verify_secret, "secret not verified");
         }
     }
+
+    #[test]
+    fn simple_scrape_reject_zero_id() {
+        let (t, n) = (2, 4);
+        let (pubs, keys): (Vec<PublicKey>, Vec<PrivateKey>) =
+            (0..n).map(|_| crypto::create_keypair()).unzip();
+
+        // id 0 is the secret, so no share verifies or recovers with it
+        let escrow = simple::escrow(t);
+        let commitments = simple::commitments(&escrow);
+        let mut decrypted = Vec::new();
+        for share in simple::create_shares(&escrow, &pubs) {
+            let idx = (share.id - 1) as usize;
+            assert!(!share.verify(0, &pubs[idx], &escrow.extra_generator, &commitments));
+            decrypted.push(simple::decrypt_share(&keys[idx], &pubs[idx], &share));
+        }
+        assert!(simple::recover(t, &decrypted).unwrap() == escrow.secret);
+        decrypted[0].id = 0;
+        assert!(simple::recover(t, &decrypted).is_err());
+
+        let escrow = scrape::escrow(t);
+        let public_shares = scrape::create_shares(&escrow, &pubs);
+        let mut decrypted: Vec<_> = public_shares
+            .encrypted_shares
+            .iter()
+            .map(|share| {
+                let idx = (share.id - 1) as usize;
+                scrape::decrypt_share(&keys[idx], &pubs[idx], share)
+            })
+            .collect();
+        assert!(scrape::recover(t, &decrypted).unwrap() == escrow.secret);
+        decrypted[0].id = 0;
+        assert!(scrape::recover(t, &decrypted).is_err());
+    }
+}
+
+#[cfg(all(test, not(feature = "std")))]
+mod no_std_tests {
+    use super::crypto::*;
+    use super::dleq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    #[test]
+    fn arithmetic_without_std() {
+        let mut rng = ChaCha20Rng::from_seed([7u8; 32]);
+        let a = Scalar::rand(&mut rng);
+        let b = Scalar::rand(&mut rng);
+        let g = Point::generator();
+        assert!(Point::from_scalar(&(a.clone() + b.clone())) == g.mul(&a) + g.mul(&b));
+        assert!(a.clone() * a.inverse() == Scalar::from_u32(1));
+
+        let h = Point::generator_h();
+        let proof = dleq::prove(&g, &h, &a, &mut rng);
+        assert!(dleq::verify(&proof, &g, &h, &g.mul(&a), &h.mul(&a)));
+        assert!(!dleq::verify(&proof, &g, &h, &g.mul(&b), &h.mul(&a)));
+    }
 }
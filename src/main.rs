@@ -18,7 +18,7 @@ impl<'a> fmt::Display for PrettySlice<'a> {
 /// defaults cannot otherwise be avoided.
 pub trait ToPretty {
     /// Convert a type into a derivative form in order to make `format!` print it prettily.
-    fn pretty(&self) -> PrettySlice;
+    fn pretty(&self) -> PrettySlice<'_>;
     /// Express the object as a hex string.
     fn to_hex(&self) -> String {
         format!("{}", self.pretty())
@@ -26,7 +26,7 @@ pub trait ToPretty {
 }
 
 impl<T: AsRef<[u8]>> ToPretty for T {
-    fn pretty(&self) -> PrettySlice {
+    fn pretty(&self) -> PrettySlice<'_> {
         PrettySlice(self.as_ref())
     }
 }
@@ -46,13 +46,13 @@ fn main() {
     // Round trip public key through bytes
     let pub_bytes = pubs[1].to_bytes();
     println!("pub_bytes = {}", pub_bytes.to_hex());
-    let pub_0 = pvss::crypto::PublicKey::from_bytes(&pub_bytes);
+    let pub_0 = pvss::crypto::PublicKey::from_bytes(&pub_bytes).unwrap();
     assert!(pub_0 == pubs[1]);
 
     // Round trip private key through bytes
     let priv_bytes = keys[1].to_bytes();
     println!("priv_bytes = {}", priv_bytes.to_hex());
-    let priv_0 = pvss::crypto::PrivateKey::from_bytes(&priv_bytes);
+    let priv_0 = pvss::crypto::PrivateKey::from_bytes(&priv_bytes).unwrap();
     assert!(priv_0 == keys[1]);
 
     let escrow = pvss::simple::escrow(t);
@@ -1,11 +1,14 @@
 // Math module define polynomial types and operations that is used to setup the scheme.
 use super::crypto;
+use alloc::vec::Vec;
+use rand::{CryptoRng, RngCore};
 
 pub struct Polynomial {
     pub elements: Vec<crypto::Scalar>,
 }
 impl Polynomial {
     /// generate a new polynomial of specific degree
+    #[cfg(feature = "std")]
     pub fn generate(degree: u32) -> Polynomial {
         let vec_size = degree + 1;
         let mut vec = Vec::with_capacity(vec_size as usize);
@@ -17,20 +20,70 @@ impl Polynomial {
         Polynomial { elements: vec }
     }
 
+    /// generate a new polynomial of specific degree from the given rng
+    pub fn random<R: RngCore + CryptoRng>(degree: u32, rng: &mut R) -> Polynomial {
+        let elements = (0..=degree)
+            .map(|_| crypto::Scalar::generate_with(rng))
+            .collect();
+        Polynomial { elements }
+    }
+
+    /// a random polynomial of specific degree whose constant term is `secret`,
+    /// as used to share it
+    pub fn random_with_secret<R: RngCore + CryptoRng>(
+        degree: u32,
+        secret: crypto::Scalar,
+        rng: &mut R,
+    ) -> Polynomial {
+        let mut elements = Vec::with_capacity(degree as usize + 1);
+        elements.push(secret);
+        for _ in 0..degree {
+            elements.push(crypto::Scalar::generate_with(rng));
+        }
+        Polynomial { elements }
+    }
+
+    /// the coefficients a0, a1, .., an
+    pub fn coefficients(&self) -> &[crypto::Scalar] {
+        &self.elements
+    }
+
     pub fn len(&self) -> usize {
         self.elements.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    pub fn degree(&self) -> usize {
+        self.elements.len().saturating_sub(1)
+    }
+
     /// get the value of a polynomial a0 + a1 * x^1 + a2 * x^2 + .. + an * x^n for a value x=at
     pub fn evaluate(&self, at: crypto::Scalar) -> crypto::Scalar {
-        let mut r = crypto::Scalar::from_u32(0);
-        for degree in 0..(self.elements.len()) {
-            let v = self.elements[degree].clone();
-            r = r + v * at.pow(degree as u32);
-        }
-        r
+        self.eval(&at)
     }
+
+    /// evaluate with Horner's method: a0 + x * (a1 + x * (a2 + ..))
+    pub fn eval(&self, x: &crypto::Scalar) -> crypto::Scalar {
+        self.elements
+            .iter()
+            .rev()
+            .fold(crypto::Scalar::zero(), |acc, a| acc * x + a)
+    }
+
     pub fn at_zero(&self) -> crypto::Scalar {
         self.elements[0].clone()
     }
+
+    /// the commitments `base * a0, base * a1, .., base * an` to the coefficients
+    pub fn commit(&self, base: &crypto::Point) -> Vec<crypto::Point> {
+        self.elements.iter().map(|a| base * a).collect()
+    }
+
+    /// `commit(&Point::generator())`, using the fixed-base table
+    pub fn commit_generator(&self) -> Vec<crypto::Point> {
+        self.elements.iter().map(crypto::Point::mul_base).collect()
+    }
 }
@@ -1,6 +1,7 @@
 // Parallel DLEQ proofs
 use super::crypto::*;
 use super::dleq;
+use super::transcript::Transcript;
 
 type Challenge = Scalar;
 
@@ -12,27 +13,22 @@ pub struct Proof {
 
 impl Proof {
     pub fn create(params: &[(Scalar, Scalar, dleq::DLEQ)]) -> Proof {
-        let mut his = Vec::with_capacity(params.len() * 4);
         let mut ais = Vec::with_capacity(params.len() * 2);
         let mut zs = Vec::with_capacity(params.len());
 
-        // create the list [h1_1 ,h2_1 , h1_2 , h2_2, ... h2_n, a1_1, a2_1, .., a1_n, a2_n ]
-        // to compute the challenge
         for param in params.iter() {
-            let &(ref w, _, ref dleq) = param;
-            his.push(dleq.h1.clone());
-            his.push(dleq.h2.clone());
-            ais.push(dleq.g1.mul(&w));
-            ais.push(dleq.g2.mul(&w));
+            let (w, _, dleq) = param;
+            ais.push(dleq.g1.mul(w));
+            ais.push(dleq.g2.mul(w));
         }
 
         // compute the challenge
-        his.append(&mut ais);
-        let c = Scalar::hash_points(his);
+        let dleqs: Vec<&dleq::DLEQ> = params.iter().map(|(_, _, dleq)| dleq).collect();
+        let c = challenge(&dleqs, &ais);
 
         // finally create each proofs
         for param in params.iter() {
-            let &(ref w, ref a, _) = param;
+            let (w, a, _) = param;
             let z = w.clone() + a.clone() * c.clone();
             zs.push(z);
         }
@@ -40,7 +36,6 @@ impl Proof {
     }
 
     pub fn verify(&self, dleqs: &[dleq::DLEQ]) -> bool {
-        let mut his = Vec::new();
         let mut ais = Vec::new();
 
         if dleqs.len() != self.zs.len() {
@@ -55,15 +50,27 @@ impl Proof {
             let r2 = dleq.g2.mul(z);
             let a1 = r1 - dleq.h1.mul(&self.c);
             let a2 = r2 - dleq.h2.mul(&self.c);
-            his.push(dleq.h1.clone());
-            his.push(dleq.h2.clone());
             ais.push(a1);
             ais.push(a2);
         }
 
-        his.append(&mut ais);
-        let c = Scalar::hash_points(his);
+        let dleqs: Vec<&dleq::DLEQ> = dleqs.iter().collect();
+        self.c == challenge(&dleqs, &ais)
+    }
+}
 
-        self.c == c
+// every statement, then the commitments `a1_i, a2_i` in order
+fn challenge(dleqs: &[&dleq::DLEQ], ais: &[Point]) -> Scalar {
+    let mut transcript = Transcript::new(b"pdleq");
+    for dleq in dleqs {
+        transcript.append_point(b"g1", &dleq.g1);
+        transcript.append_point(b"h1", &dleq.h1);
+        transcript.append_point(b"g2", &dleq.g2);
+        transcript.append_point(b"h2", &dleq.h2);
+    }
+    for pair in ais.chunks(2) {
+        transcript.append_point(b"a1", &pair[0]);
+        transcript.append_point(b"a2", &pair[1]);
     }
+    transcript.challenge_scalar(b"c")
 }
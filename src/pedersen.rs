@@ -0,0 +1,54 @@
+// Pedersen commitments `G * value + H * blinding`, hiding through the
+// blinding and binding since the discrete log of `H` relative to `G` is unknown
+use core::ops::Add;
+
+use super::crypto::*;
+
+/// the second base of the commitments, `Point::generator_h`
+pub fn generator_h() -> Point {
+    Point::generator_h()
+}
+
+pub fn commit(value: &Scalar, blinding: &Scalar) -> Point {
+    Point::from_scalar(value) + generator_h().mul(blinding)
+}
+
+pub fn verify(commitment: &Point, value: &Scalar, blinding: &Scalar) -> bool {
+    *commitment == commit(value, blinding)
+}
+
+/// the commitment to the sum of the values, with the sum of the blindings
+pub fn add(c1: &Point, c2: &Point) -> Point {
+    c1 + c2
+}
+
+/// the value and blinding of a commitment. openings add up like the
+/// commitments they open.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Opening {
+    pub value: Scalar,
+    pub blinding: Scalar,
+}
+
+impl Opening {
+    pub fn new(value: Scalar, blinding: Scalar) -> Opening {
+        Opening { value, blinding }
+    }
+
+    pub fn commit(&self) -> Point {
+        commit(&self.value, &self.blinding)
+    }
+}
+
+impl Add for Opening {
+    type Output = Opening;
+
+    fn add(self, other: Opening) -> Opening {
+        Opening::new(self.value + other.value, self.blinding + other.blinding)
+    }
+}
+
+/// check that `opening` opens `commitment`
+pub fn open(commitment: &Point, opening: &Opening) -> bool {
+    verify(commitment, &opening.value, &opening.blinding)
+}
@@ -0,0 +1,538 @@
+// Publicly verifiable secret sharing of a scalar, following Schoenmakers:
+// the dealer commits to the sharing polynomial, encrypts each share to its
+// participant and proves that the encrypted shares match the commitments.
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use rand::rngs::StdRng;
+use rand::{CryptoRng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use super::crypto::ark::point_error;
+use super::crypto::*;
+use super::dleq::{self, DleqProof};
+use super::elgamal;
+use super::error::{PvssError, VerifyError};
+use super::math::Polynomial;
+use super::sharing::{self, participant_index, ShareParams};
+use super::transcript::Transcript;
+
+#[derive(Clone, Debug)]
+pub struct EncryptedShare {
+    /// index of the participant, from 1 to n
+    pub index: usize,
+    /// `pk * f(index)`
+    pub value: Point,
+    /// proves that `log_G(G * f(index)) == log_pk(value)`
+    pub proof: DleqProof,
+}
+
+/// everything the dealer publishes
+#[derive(Clone, Debug)]
+pub struct Dealing {
+    pub threshold: usize,
+    /// `G * a_j` for the coefficients `a_j` of the sharing polynomial
+    pub commitments: Vec<Point>,
+    pub shares: Vec<EncryptedShare>,
+}
+
+pub type PvssTranscript = Dealing;
+
+// sizes of the parts of `Dealing::to_bytes`
+const POINT_COMPRESSED_BYTES: usize = 48;
+const PROOF_BYTES: usize = 128;
+
+// `participant_index` of an index the caller has already checked
+fn index_scalar(index: usize) -> Scalar {
+    sharing::try_participant_index(index).expect("invalid participant index")
+}
+
+fn dealing_bytes(threshold: usize, n: usize) -> Option<usize> {
+    let commitments = threshold.checked_mul(POINT_COMPRESSED_BYTES)?;
+    let shares = n.checked_mul(POINT_COMPRESSED_BYTES + PROOF_BYTES)?;
+    commitments.checked_add(shares)?.checked_add(8)
+}
+
+pub struct Dealer;
+
+impl Dealer {
+    /// share `secret` between the participants, any `threshold` of which can
+    /// recover `G * secret`. with the `rayon` feature the shares are computed
+    /// in parallel, giving the same dealing as the serial version.
+    pub fn deal<R: RngCore + CryptoRng>(
+        secret: &Scalar,
+        participant_pks: &[PublicKey],
+        threshold: usize,
+        rng: &mut R,
+    ) -> Result<Dealing, PvssError> {
+        Self::deal_with(secret, participant_pks, threshold, rng, cfg!(feature = "rayon"))
+    }
+
+    pub(crate) fn deal_with<R: RngCore + CryptoRng>(
+        secret: &Scalar,
+        participant_pks: &[PublicKey],
+        threshold: usize,
+        rng: &mut R,
+        parallel: bool,
+    ) -> Result<Dealing, PvssError> {
+        let params = ShareParams::new(threshold, participant_pks.len())?;
+        let poly = Polynomial::random_with_secret(params.degree(), secret.clone(), rng);
+        let g = Point::generator();
+        let commitments = poly.commit_generator();
+        // every share draws its proof nonce from its own stream, so that the
+        // dealing does not depend on the order in which shares are computed
+        let mut seed = [0u8; 32];
+        rng.fill_bytes(&mut seed);
+        let shares = map_participants(participant_pks, parallel, |i, pk| {
+            let index = i + 1;
+            let mut rng = ChaCha20Rng::from_seed(seed);
+            rng.set_stream(index as u64);
+            let share = poly.eval(&participant_index(index as u32));
+            EncryptedShare {
+                index,
+                value: elgamal::encrypt_share(pk, &share),
+                proof: dleq::prove(&g, &pk.point, &share, &mut rng),
+            }
+        });
+        Ok(Dealing {
+            threshold,
+            commitments,
+            shares,
+        })
+    }
+}
+
+#[cfg(feature = "rayon")]
+fn map_participants<T, F>(pks: &[PublicKey], parallel: bool, f: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(usize, &PublicKey) -> T + Sync,
+{
+    if parallel {
+        pks.par_iter().enumerate().map(|(i, pk)| f(i, pk)).collect()
+    } else {
+        pks.iter().enumerate().map(|(i, pk)| f(i, pk)).collect()
+    }
+}
+
+#[cfg(not(feature = "rayon"))]
+fn map_participants<T, F>(pks: &[PublicKey], _parallel: bool, f: F) -> Vec<T>
+where
+    F: Fn(usize, &PublicKey) -> T,
+{
+    pks.iter().enumerate().map(|(i, pk)| f(i, pk)).collect()
+}
+
+/// share `secret` between the owners of `pubkeys`, any `threshold` of which
+/// can recover `G * secret`. the errors are those of `Dealer::deal`.
+#[cfg(feature = "std")]
+pub fn deal(
+    secret: &Scalar,
+    pubkeys: &[PublicKey],
+    threshold: usize,
+) -> Result<Dealing, PvssError> {
+    Dealer::deal(secret, pubkeys, threshold, &mut StdRng::from_entropy())
+}
+
+impl Dealing {
+    /// `G * secret`, the commitment to the constant term. summed over the
+    /// dealers of a distributed key generation it is the group public key,
+    /// without the secret ever being reconstructed. `InvalidDealing` if there
+    /// are no commitments.
+    pub fn public_commitment(&self) -> Result<Point, PvssError> {
+        self.commitments.first().cloned().ok_or(PvssError::InvalidDealing)
+    }
+
+    /// `G * f(index)`, computed from the commitments alone. panics if `index`
+    /// is not a participant index; `public_commitment` is the value at 0.
+    pub fn share_commitment(&self, index: usize) -> Point {
+        eval_commitments(&self.commitments, &index_scalar(index))
+    }
+
+    /// check, without any secret, that every participant got an encryption
+    /// of its share of the committed polynomial
+    pub fn verify(&self, participant_pks: &[PublicKey]) -> bool {
+        if self.threshold == 0
+            || self.commitments.len() != self.threshold
+            || self.shares.len() != participant_pks.len()
+            || self.threshold > self.shares.len()
+        {
+            return false;
+        }
+        if self.shares.iter().enumerate().any(|(i, share)| share.index != i + 1) {
+            return false;
+        }
+        let g = Point::generator();
+        let proofs: Vec<DleqProof> = self.shares.iter().map(|s| s.proof.clone()).collect();
+        let instances = map_participants(participant_pks, cfg!(feature = "rayon"), |i, pk| {
+            let share = &self.shares[i];
+            (
+                g.clone(),
+                self.share_commitment(share.index),
+                pk.point.clone(),
+                share.value.clone(),
+            )
+        });
+        // the weights of the batch check only need to be unpredictable to the
+        // dealer, so they are derived from everything it published
+        let mut transcript = Transcript::new(b"dealing-weights");
+        for (instance, proof) in instances.iter().zip(&proofs) {
+            transcript.append_point(b"commitment", &instance.1);
+            transcript.append_point(b"pk", &instance.2);
+            transcript.append_point(b"share", &instance.3);
+            transcript.append_message(b"proof", &proof.to_bytes());
+        }
+        let seed = transcript.challenge_scalar(b"seed").to_bytes_canonical();
+        dleq::batch_verify(&proofs, &instances, &mut ChaCha20Rng::from_seed(seed))
+    }
+
+    /// check the share of the participant `index` alone, e.g. to stop at the
+    /// first bad share or to find it once `verify` failed
+    pub fn verify_share(&self, index: usize, pubkeys: &[PublicKey]) -> Result<(), VerifyError> {
+        if self.threshold == 0 || self.commitments.len() != self.threshold {
+            return Err(VerifyError::InvalidDealing);
+        }
+        let out_of_range = VerifyError::IndexOutOfRange { index };
+        let i = index.checked_sub(1).ok_or(out_of_range)?;
+        let (share, pk) = match (self.shares.get(i), pubkeys.get(i)) {
+            (Some(share), Some(pk)) => (share, pk),
+            _ => return Err(out_of_range),
+        };
+        let valid = share.index == index
+            && share.proof.verify(
+                &Point::generator(),
+                &self.share_commitment(index),
+                &pk.point,
+                &share.value,
+            );
+        if !valid {
+            return Err(VerifyError::InvalidProof { index });
+        }
+        Ok(())
+    }
+
+    /// compact encoding: the threshold and the number of shares as u32 little
+    /// endian, the compressed commitments, then for each share its compressed
+    /// value and its proof. the index of a share is its position, as
+    /// `verify` requires.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut points = self.commitments.clone();
+        points.extend(self.shares.iter().map(|s| s.value.clone()));
+        let points = Point::batch_to_bytes_compressed(&points);
+        let (commitments, values) = points.split_at(self.commitments.len());
+
+        let size = dealing_bytes(self.threshold, self.shares.len()).unwrap_or(0);
+        let mut buf = Vec::with_capacity(size);
+        buf.extend_from_slice(&(self.threshold as u32).to_le_bytes());
+        buf.extend_from_slice(&(self.shares.len() as u32).to_le_bytes());
+        for c in commitments {
+            buf.extend_from_slice(c);
+        }
+        for (value, share) in values.iter().zip(&self.shares) {
+            buf.extend_from_slice(value);
+            buf.extend_from_slice(&share.proof.to_bytes());
+        }
+        buf
+    }
+
+    /// decode the output of `to_bytes`. the counts are checked to be valid
+    /// share parameters, with the errors of `ShareParams::new`, and against
+    /// the length of the input before anything is allocated: `InvalidDealing`
+    /// for a wrong length.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Dealing, PvssError> {
+        if bytes.len() < 8 {
+            return Err(PvssError::InvalidDealing);
+        }
+        let (counts, mut rest) = bytes.split_at(8);
+        let threshold = u32::from_le_bytes([counts[0], counts[1], counts[2], counts[3]]) as usize;
+        let n = u32::from_le_bytes([counts[4], counts[5], counts[6], counts[7]]) as usize;
+        ShareParams::new(threshold, n)?;
+        if dealing_bytes(threshold, n) != Some(bytes.len()) {
+            return Err(PvssError::InvalidDealing);
+        }
+        let mut take = |len: usize| {
+            let (head, tail) = rest.split_at(len);
+            rest = tail;
+            head
+        };
+        let commitments = (0..threshold)
+            .map(|_| Point::from_compressed(take(POINT_COMPRESSED_BYTES)).map_err(point_error))
+            .collect::<Result<Vec<_>, _>>()?;
+        let shares = (0..n)
+            .map(|i| {
+                let value =
+                    Point::from_compressed(take(POINT_COMPRESSED_BYTES)).map_err(point_error)?;
+                let proof = DleqProof::from_bytes(take(PROOF_BYTES))?;
+                Ok(EncryptedShare {
+                    index: i + 1,
+                    value,
+                    proof,
+                })
+            })
+            .collect::<Result<Vec<_>, PvssError>>()?;
+        Ok(Dealing {
+            threshold,
+            commitments,
+            shares,
+        })
+    }
+
+    /// sum dealings to the same participants into a dealing of the sum of
+    /// their secrets, as in distributed key generation. every dealing is
+    /// checked against `participant_pks` first, which also rejects dealings
+    /// to other participants.
+    pub fn aggregate(
+        dealings: &[Dealing],
+        participant_pks: &[PublicKey],
+    ) -> Result<AggregateDealing, PvssError> {
+        let first = dealings.first().ok_or(PvssError::InvalidDealing)?;
+        if dealings.iter().any(|d| d.threshold != first.threshold) {
+            return Err(PvssError::InvalidThreshold);
+        }
+        if !dealings.iter().all(|d| d.verify(participant_pks)) {
+            return Err(PvssError::InvalidDealing);
+        }
+        let (commitments, shares) = sum_dealings(dealings, first.threshold, participant_pks.len());
+        Ok(AggregateDealing {
+            threshold: first.threshold,
+            commitments,
+            shares,
+            dealings: dealings.to_vec(),
+        })
+    }
+}
+
+// the component-wise sums of the commitments and encrypted shares of dealings
+// already checked to have `threshold` commitments and `n` shares
+fn sum_dealings(dealings: &[Dealing], threshold: usize, n: usize) -> (Vec<Point>, Vec<Point>) {
+    let commitments = (0..threshold)
+        .map(|j| dealings.iter().map(|d| &d.commitments[j]).sum())
+        .collect();
+    let shares = (0..n)
+        .map(|i| dealings.iter().map(|d| &d.shares[i].value).sum())
+        .collect();
+    (commitments, shares)
+}
+
+/// the sum of verified dealings, from `Dealing::aggregate`. the DLEQ proofs
+/// of the dealings do not add up into proofs of the sums, so this is not a
+/// `Dealing`: it keeps the dealings it sums, and `verify` checks their proofs
+/// along with the sums.
+#[derive(Clone, Debug)]
+pub struct AggregateDealing {
+    pub threshold: usize,
+    /// the sums of the commitments of the dealings
+    pub commitments: Vec<Point>,
+    /// `pk * F(index)` for the participant of index `i + 1`, where `F` is the
+    /// sum of the polynomials of the dealings
+    pub shares: Vec<Point>,
+    /// the dealings summed, with their proofs
+    pub dealings: Vec<Dealing>,
+}
+
+impl AggregateDealing {
+    /// `G * (sum of the secrets)`, the group public key of the dealers.
+    /// `InvalidDealing` if there are no commitments.
+    pub fn public_commitment(&self) -> Result<Point, PvssError> {
+        self.commitments.first().cloned().ok_or(PvssError::InvalidDealing)
+    }
+
+    /// check, without any secret, that every dealing is valid for
+    /// `participant_pks` with the threshold of the aggregate, and that the
+    /// commitments and shares are their sums
+    pub fn verify(&self, participant_pks: &[PublicKey]) -> bool {
+        if self.dealings.is_empty()
+            || self.dealings.iter().any(|d| d.threshold != self.threshold)
+            || !self.dealings.iter().all(|d| d.verify(participant_pks))
+        {
+            return false;
+        }
+        let (commitments, shares) =
+            sum_dealings(&self.dealings, self.threshold, participant_pks.len());
+        commitments == self.commitments && shares == self.shares
+    }
+
+    /// `G * F(index)`, computed from the commitments alone. panics if `index`
+    /// is not a participant index.
+    pub fn share_commitment(&self, index: usize) -> Point {
+        eval_commitments(&self.commitments, &index_scalar(index))
+    }
+
+    /// recover `G * F(index)` from the aggregate share of a participant
+    pub fn decrypt_share(&self, sk: &PrivateKey, index: usize) -> Result<Point, VerifyError> {
+        let share = index
+            .checked_sub(1)
+            .and_then(|i| self.shares.get(i))
+            .ok_or(VerifyError::IndexOutOfRange { index })?;
+        Ok(elgamal::decrypt_share(sk, share))
+    }
+}
+
+// `G * f(x)` from the commitments `G * a_j` to the coefficients of `f`
+fn eval_commitments(commitments: &[Point], x: &Scalar) -> Point {
+    let powers: Vec<Scalar> = (0..commitments.len()).map(|j| x.pow(j as u32)).collect();
+    Point::multi_scalar_mul(commitments, &powers)
+}
+
+/// what an old holder publishes when it reshares: `PvssTranscript` of its
+/// share, tagged with the holder's index
+#[derive(Clone, Debug)]
+pub struct ReshareTranscript {
+    /// index of the old holder, from 1 to n
+    pub index: usize,
+    /// a dealing of `f(index)` to the new participants
+    pub dealing: Dealing,
+}
+
+/// refresh a share for proactive security: the holder `my_index` of the share
+/// `f(my_index)` of a sharing deals it again to `new_pks`. once the old
+/// threshold of holders have done so, every new participant combines its sub-shares with
+/// `combine_reshares` into a share of the same secret, and the old shares stop
+/// being useful together with the new ones.
+///
+/// `my_share` is a scalar, so the old sharing must hand out scalars, such as
+/// a Feldman sharing whose shares are checked with `sharing::verify_share`.
+/// resharing the output of `deal` is not supported: its shares decrypt to
+/// points `G * f(i)`, whose holders do not know `f(i)`. for the same reason
+/// the refreshed shares of `combine_reshares` cannot be reshared again.
+pub fn reshare<R: RngCore + CryptoRng>(
+    my_index: usize,
+    my_share: &Scalar,
+    new_pks: &[PublicKey],
+    threshold: usize,
+    rng: &mut R,
+) -> Result<ReshareTranscript, PvssError> {
+    sharing::try_participant_index(my_index)?;
+    Ok(ReshareTranscript {
+        index: my_index,
+        dealing: Dealer::deal(my_share, new_pks, threshold, rng)?,
+    })
+}
+
+/// check a reshare: it must be a valid dealing to `new_pks` of the share of
+/// the holder `reshare.index` committed to by `old_commitments`
+pub fn verify_reshare(
+    reshare: &ReshareTranscript,
+    old_commitments: &[Point],
+    new_pks: &[PublicKey],
+) -> bool {
+    let x = match sharing::try_participant_index(reshare.index) {
+        Ok(x) => x,
+        Err(_) => return false,
+    };
+    let dealing = &reshare.dealing;
+    dealing.public_commitment() == Ok(eval_commitments(old_commitments, &x))
+        && dealing.verify(new_pks)
+}
+
+/// the refreshed share `G * f'(j)` of a new participant, from the decrypted
+/// sub-shares `(i, G * g_i(j))` of the reshares by the old holders `i`, with
+/// distinct indices. `NotEnoughShares` with fewer than `old_threshold`.
+pub fn combine_reshares(
+    sub_shares: &[(usize, Point)],
+    old_threshold: usize,
+) -> Result<Point, PvssError> {
+    // the refreshed share interpolates the sub-shares like the secret does
+    // the shares
+    reconstruct(sub_shares, old_threshold)
+}
+
+/// recover `G * f(index)` from the encrypted share of a participant
+pub fn decrypt_share(sk: &PrivateKey, share: &EncryptedShare) -> Point {
+    elgamal::decrypt_share(sk, &share.value)
+}
+
+/// decrypt `encrypted = pk * s` to `S = G * s`, proving that
+/// `log_G(pk) == log_S(encrypted)` so that others can check `S`
+#[cfg(feature = "std")]
+pub fn prove_decryption(sk: &PrivateKey, encrypted: &Point) -> (Point, DleqProof) {
+    let g = Point::generator();
+    let pk = g.mul(&sk.scalar);
+    let decrypted = elgamal::decrypt_share(sk, encrypted);
+    let proof = DleqProof::create(&sk.scalar, &g, &pk, &decrypted, encrypted);
+    (decrypted, proof)
+}
+
+pub fn verify_decryption(
+    pk: &PublicKey,
+    encrypted: &Point,
+    decrypted: &Point,
+    proof: &DleqProof,
+) -> bool {
+    proof.verify(&Point::generator(), &pk.point, decrypted, encrypted)
+}
+
+/// a participant's evidence that the dealer sent it a bad share: the
+/// decryption of its share, proven correct, which does not match the
+/// commitments
+#[derive(Clone, Debug)]
+pub struct Complaint {
+    pub index: usize,
+    /// `G * s` for the encrypted share `pk * s`
+    pub decrypted: Point,
+    /// proves that `log_G(pk) == log_decrypted(pk * s)`
+    pub proof: DleqProof,
+}
+
+/// complain about the share of the participant `my_index`, owner of `sk`, if
+/// it does not decrypt to its commitment. `None` for a good share.
+#[cfg(feature = "std")]
+pub fn file_complaint(
+    sk: &PrivateKey,
+    transcript: &PvssTranscript,
+    my_index: usize,
+) -> Option<Complaint> {
+    let share = transcript.shares.get(my_index.checked_sub(1)?)?;
+    let (decrypted, proof) = prove_decryption(sk, &share.value);
+    if decrypted == transcript.share_commitment(my_index) {
+        return None;
+    }
+    Some(Complaint {
+        index: my_index,
+        decrypted,
+        proof,
+    })
+}
+
+/// check a complaint against the dealer of `transcript`, where
+/// `complainant_pk` is the public key of the participant `complaint.index`.
+/// the proof pins the decryption, so an honest dealer cannot be framed.
+pub fn verify_complaint(
+    transcript: &PvssTranscript,
+    complaint: &Complaint,
+    complainant_pk: &PublicKey,
+) -> bool {
+    let share = match complaint.index.checked_sub(1).and_then(|i| transcript.shares.get(i)) {
+        Some(share) => share,
+        None => return false,
+    };
+    verify_decryption(complainant_pk, &share.value, &complaint.decrypted, &complaint.proof)
+        && complaint.decrypted != transcript.share_commitment(complaint.index)
+}
+
+/// recover `G * secret` from `threshold` decrypted shares `(index, G * f(index))`,
+/// which must have distinct indices
+pub fn reconstruct(
+    decrypted_shares: &[(usize, Point)],
+    threshold: usize,
+) -> Result<Point, PvssError> {
+    if threshold == 0 {
+        return Err(PvssError::InvalidThreshold);
+    }
+    sharing::check_distinct_indices(decrypted_shares.iter().map(|(i, _)| *i))?;
+    let indices = decrypted_shares
+        .iter()
+        .map(|(i, _)| sharing::try_participant_index(*i))
+        .collect::<Result<Vec<Scalar>, PvssError>>()?;
+    if decrypted_shares.len() < threshold {
+        return Err(PvssError::NotEnoughShares);
+    }
+    let shares: Vec<(Scalar, Point)> = indices
+        .into_iter()
+        .zip(decrypted_shares)
+        .take(threshold)
+        .map(|(x, (_, p))| (x, p.clone()))
+        .collect();
+    sharing::lagrange_interpolate_points_at_zero(&shares)
+}
@@ -0,0 +1,66 @@
+// Schnorr proof of knowledge of a private key, e.g. for registering a public
+// key without allowing rogue keys
+use alloc::vec::Vec;
+use rand::{CryptoRng, RngCore};
+
+use super::crypto::*;
+use super::error::PvssError;
+use super::transcript::Transcript;
+
+#[derive(Clone, Debug)]
+pub struct SchnorrProof {
+    c: Scalar,
+    z: Scalar,
+}
+
+impl SchnorrProof {
+    /// prove knowledge of `sk`, the discrete log of its public key
+    #[cfg(feature = "std")]
+    pub fn create(sk: &PrivateKey) -> SchnorrProof {
+        Self::create_with_nonce(Scalar::generate(), sk)
+    }
+
+    pub fn create_with<R: RngCore + CryptoRng>(sk: &PrivateKey, rng: &mut R) -> SchnorrProof {
+        Self::create_with_nonce(Scalar::generate_with(rng), sk)
+    }
+
+    fn create_with_nonce(w: Scalar, sk: &PrivateKey) -> SchnorrProof {
+        let pk = Point::from_scalar(&sk.scalar);
+        let c = challenge(&pk, &Point::from_scalar(&w));
+        let z = w + &sk.scalar * &c;
+        SchnorrProof { c, z }
+    }
+
+    pub fn verify(&self, pk: &PublicKey) -> bool {
+        let a = Point::from_scalar(&self.z) - pk.point.mul(&self.c);
+        self.c == challenge(&pk.point, &a)
+    }
+
+    /// the challenge followed by the response, in their canonical encoding
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [self.c.to_bytes_canonical(), self.z.to_bytes_canonical()].concat()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<SchnorrProof, PvssError> {
+        if bytes.len() != 64 {
+            return Err(PvssError::InvalidScalarEncoding);
+        }
+        let mut c = [0u8; 32];
+        let mut z = [0u8; 32];
+        c.copy_from_slice(&bytes[..32]);
+        z.copy_from_slice(&bytes[32..]);
+        Ok(SchnorrProof {
+            c: Scalar::from_bytes_canonical(&c)?,
+            z: Scalar::from_bytes_canonical(&z)?,
+        })
+    }
+}
+
+// the statement and the commitment `a = G * w`
+fn challenge(pk: &Point, a: &Point) -> Scalar {
+    let mut transcript = Transcript::new(b"schnorr");
+    transcript.append_point(b"g", &Point::generator());
+    transcript.append_point(b"pk", pk);
+    transcript.append_point(b"a", a);
+    transcript.challenge_scalar(b"c")
+}
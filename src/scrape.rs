@@ -5,6 +5,7 @@ use super::crypto::*;
 use super::dleq;
 use super::math;
 use super::pdleq;
+use super::sharing::participant_index;
 use super::types::*;
 
 pub type Secret = Point;
@@ -63,14 +64,7 @@ pub fn escrow(t: Threshold) -> Escrow {
     let secret = poly.at_zero();
     let g_s = Point::from_scalar(&secret);
 
-    let challenge = Scalar::generate();
-    let dleq = dleq::DLEQ {
-        g1: Point::generator(),
-        h1: g_s.clone(),
-        g2: gen.clone(),
-        h2: gen.mul(&secret),
-    };
-    let proof = dleq::Proof::create(challenge, secret, dleq);
+    let proof = dleq::Proof::create(&secret, &Point::generator(), &g_s, &gen, &gen.mul(&secret));
 
     Escrow {
         threshold: t,
@@ -92,7 +86,7 @@ pub fn create_shares(escrow: &Escrow, pubs: &[PublicKey]) -> PublicShares {
         let eval_point = i + 1;
         let si = escrow
             .polynomial
-            .evaluate(Scalar::from_u32(eval_point as u32));
+            .evaluate(participant_index(eval_point as u32));
         let esi = public.point.mul(&si);
         let vi = escrow.extra_generator.mul(&si);
 
@@ -152,37 +146,61 @@ impl PublicShares {
         }
 
         // reed solomon check
-        let n = self.number_participants();
-        let poly = math::Polynomial::generate(n - self.threshold - 1);
+        let n = self.number_participants() as usize;
+        let points: Vec<Point> = self.commitments[..n]
+            .iter()
+            .map(|c| c.point.clone())
+            .collect();
+        scrape_check(&points, self.threshold as usize)
+    }
+}
 
-        let mut v = Point::infinity();
-        for i in 0..n {
-            let idx = i as usize;
+// check that the commitments `C_i`, for the evaluation points 1..n, are the
+// commitments of a polynomial of degree at most `threshold - 1`: for a
+// codeword `v` of the dual reed solomon code, `sum v_i * C_i` is the identity.
+// the codeword comes from a polynomial of degree `n - threshold - 1` whose
+// coefficients are the powers of a hash of the commitments.
+pub fn scrape_check(commitments: &[Point], threshold: usize) -> bool {
+    let n = commitments.len();
+    if threshold == 0 || threshold > n {
+        return false;
+    }
+    if threshold == n {
+        // every vector of n commitments is a codeword
+        return true;
+    }
+    let seed = Scalar::hash_points(commitments.to_vec());
+    let poly = math::Polynomial {
+        elements: (0..(n - threshold) as u32).map(|k| seed.pow(k)).collect(),
+    };
 
-            let mut cperp = poly.evaluate(Scalar::from_u32(i));
-            for j in 0..n {
-                if i != j {
-                    cperp = cperp * (Scalar::from_u32(i) - Scalar::from_u32(j)).inverse();
-                }
+    let xs: Vec<Scalar> = (1..=n as u32).map(participant_index).collect();
+    let mut denominators = Vec::with_capacity(n);
+    for (i, xi) in xs.iter().enumerate() {
+        let mut d = Scalar::multiplicative_identity();
+        for (j, xj) in xs.iter().enumerate() {
+            if i != j {
+                d *= xi - xj;
             }
-
-            let commitment = &self.commitments[idx];
-            v = v + commitment.point.mul(&cperp);
         }
-
-        v == Point::infinity()
+        denominators.push(d);
     }
+    let cperps: Vec<Scalar> = Scalar::batch_inverse(&denominators)
+        .into_iter()
+        .zip(xs)
+        .map(|(d, x)| poly.evaluate(x) * d)
+        .collect();
+    Point::multi_scalar_mul(commitments, &cperps).is_identity()
 }
 
 impl DecryptedShare {
     pub fn verify(&self, public: &PublicKey, eshare: &EncryptedShare) -> bool {
-        let dleq = dleq::DLEQ {
-            g1: Point::generator(),
-            h1: public.point.clone(),
-            g2: self.decrypted_val.clone(),
-            h2: eshare.encrypted_val.clone(),
-        };
-        self.proof.verify(dleq)
+        self.proof.verify(
+            &Point::generator(),
+            &public.point,
+            &self.decrypted_val,
+            &eshare.encrypted_val,
+        )
     }
 }
 
@@ -191,18 +209,11 @@ pub fn decrypt_share(
     public: &PublicKey,
     share: &EncryptedShare,
 ) -> DecryptedShare {
-    let challenge = Scalar::generate();
     let xi = private.scalar.clone();
     let yi = public.point.clone();
     let lifted_yi = share.encrypted_val.clone();
     let si = lifted_yi.mul(&xi.inverse());
-    let dleq = dleq::DLEQ {
-        g1: Point::generator(),
-        h1: yi,
-        g2: si.clone(),
-        h2: lifted_yi,
-    };
-    let proof = dleq::Proof::create(challenge, xi, dleq);
+    let proof = dleq::Proof::create(&xi, &Point::generator(), &yi, &si, &lifted_yi);
     DecryptedShare {
         id: share.id,
         decrypted_val: si,
@@ -214,26 +225,29 @@ fn interpolate_one(t: Threshold, sid: usize, shares: &[DecryptedShare]) -> Scala
     let mut v = Scalar::multiplicative_identity();
     for j in 0..(t as usize) {
         if j != sid {
-            let sj = Scalar::from_u32(shares[j].id);
-            let si = Scalar::from_u32(shares[sid].id);
-            let d = sj.clone() - si;
-            v = v * sj * d.inverse();
+            let sj = participant_index(shares[j].id);
+            let si = participant_index(shares[sid].id);
+            let d = &sj - &si;
+            v = v * sj / d;
         }
     }
     v
 }
 
 // Try to recover a secret
+#[allow(clippy::result_unit_err)]
 pub fn recover(t: Threshold, shares: &[DecryptedShare]) -> Result<Secret, ()> {
-    if t as usize > shares.len() {
+    if t as usize > shares.len() || shares.iter().any(|s| s.id == 0) {
         return Err(());
     };
-    let mut result = Point::infinity();
-    for i in 0..(t as usize) {
-        let v = interpolate_one(t, i, shares);
-        result = result + shares[i].decrypted_val.mul(&v);
-    }
-    Ok(result)
+    let points: Vec<Point> = shares[..t as usize]
+        .iter()
+        .map(|s| s.decrypted_val.clone())
+        .collect();
+    let scalars: Vec<Scalar> = (0..(t as usize))
+        .map(|i| interpolate_one(t, i, shares))
+        .collect();
+    Ok(Point::multi_scalar_mul(&points, &scalars))
 }
 
 pub fn verify_secret(secret: Secret, public_shares: &PublicShares) -> bool {
@@ -244,21 +258,20 @@ pub fn verify_secret(secret: Secret, public_shares: &PublicShares) -> bool {
             let mut v = Scalar::multiplicative_identity();
             for j in 0..(public_shares.threshold as usize) {
                 if j != i {
-                    let sj = Scalar::from_u32((j + 1) as u32);
-                    let si = Scalar::from_u32((i + 1) as u32);
-                    let d = sj.clone() - si;
-                    v = v * sj * d.inverse();
+                    let sj = participant_index((j + 1) as u32);
+                    let si = participant_index((i + 1) as u32);
+                    let d = &sj - &si;
+                    v = v * sj / d;
                 }
             }
             v
         };
-        commitment_interpolate = commitment_interpolate + x.mul(&li);
+        commitment_interpolate += x.mul(&li);
     }
-    let dleq = dleq::DLEQ {
-        g1: Point::generator(),
-        h1: secret,
-        g2: public_shares.extra_generator.clone(),
-        h2: commitment_interpolate,
-    };
-    public_shares.secret_proof.verify(dleq)
+    public_shares.secret_proof.verify(
+        &Point::generator(),
+        &secret,
+        &public_shares.extra_generator,
+        &commitment_interpolate,
+    )
 }
@@ -0,0 +1,179 @@
+// Shamir secret sharing of scalars, without any encryption or proof
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use rand::{CryptoRng, RngCore};
+
+use super::crypto::{Point, Scalar};
+use super::error::PvssError;
+use super::math::Polynomial;
+
+/// a threshold and a number of participants with `1 <= threshold <= n`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShareParams {
+    threshold: usize,
+    n: usize,
+}
+
+impl ShareParams {
+    pub fn new(threshold: usize, n: usize) -> Result<ShareParams, PvssError> {
+        if n == 0 {
+            return Err(PvssError::NoParticipants);
+        }
+        if threshold == 0 || threshold > n {
+            return Err(PvssError::InvalidThreshold);
+        }
+        Ok(ShareParams { threshold, n })
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// the degree of the sharing polynomial, `threshold - 1`
+    pub fn degree(&self) -> u32 {
+        (self.threshold - 1) as u32
+    }
+}
+
+/// the x coordinate of the participant `i`. participants are numbered from
+/// 1, since the value at 0 is the secret; every dealing, verification and
+/// reconstruction goes through this mapping. panics if `i` is 0.
+pub fn participant_index(i: u32) -> Scalar {
+    assert!(i != 0, "participant index 0 is the secret");
+    Scalar::from_u32(i)
+}
+
+/// `participant_index` of an untrusted index, rejecting 0 and any index that
+/// does not fit in a u32 instead of panicking or truncating it
+pub fn try_participant_index(i: usize) -> Result<Scalar, PvssError> {
+    match u32::try_from(i) {
+        Ok(0) => Err(PvssError::ZeroIndex),
+        Ok(i) => Ok(participant_index(i)),
+        Err(_) => Err(PvssError::IndexTooLarge),
+    }
+}
+
+/// split `secret` into `n` shares `(index, share)`, any `threshold` of which
+/// recover it. the secret is the constant term of a random polynomial of
+/// degree `threshold - 1`, evaluated at the indices 1 to n.
+pub fn split_secret<R: RngCore + CryptoRng>(
+    secret: &Scalar,
+    threshold: usize,
+    n: usize,
+    rng: &mut R,
+) -> Result<Vec<(Scalar, Scalar)>, PvssError> {
+    let params = ShareParams::new(threshold, n)?;
+    let poly = Polynomial::random_with_secret(params.degree(), secret.clone(), rng);
+    Ok((1..=params.n())
+        .map(|i| {
+            let x = participant_index(i as u32);
+            let share = poly.evaluate(x.clone());
+            (x, share)
+        })
+        .collect())
+}
+
+/// recover the secret from `threshold` of the shares, by Lagrange
+/// interpolation at zero. extra shares are ignored, but all of the indices
+/// must be distinct.
+pub fn reconstruct(shares: &[(Scalar, Scalar)], threshold: usize) -> Result<Scalar, PvssError> {
+    if threshold == 0 {
+        return Err(PvssError::InvalidThreshold);
+    }
+    check_distinct_indices(shares.iter().map(|(x, _)| x.to_bytes_canonical()))?;
+    if shares.iter().any(|(x, _)| x.is_zero()) {
+        return Err(PvssError::ZeroIndex);
+    }
+    if shares.len() < threshold {
+        return Err(PvssError::NotEnoughShares);
+    }
+    lagrange_interpolate_at_zero(&shares[..threshold])
+}
+
+// a repeated index means a share was replayed, even if it would not end up
+// among the ones interpolated
+pub(crate) fn check_distinct_indices<T: Ord>(
+    indices: impl IntoIterator<Item = T>,
+) -> Result<(), PvssError> {
+    let mut seen = BTreeSet::new();
+    if indices.into_iter().all(|i| seen.insert(i)) {
+        Ok(())
+    } else {
+        Err(PvssError::DuplicateIndex)
+    }
+}
+
+/// the value at zero of the polynomial through the points `(x, y)`, of degree
+/// below the number of points. `x` is the index of the participant as given
+/// by `split_secret`, `participant_index(i)` for `i` from 1, so any subset of
+/// the shares works and not only the first ones.
+pub fn lagrange_interpolate_at_zero(points: &[(Scalar, Scalar)]) -> Result<Scalar, PvssError> {
+    if points.is_empty() {
+        return Err(PvssError::NotEnoughShares);
+    }
+    let indices: Vec<Scalar> = points.iter().map(|(x, _)| x.clone()).collect();
+    let coefficients = lagrange_coefficients(&indices, &Scalar::zero())?;
+    Ok(coefficients
+        .into_iter()
+        .zip(points.iter())
+        .map(|(l, (_, y))| l * y)
+        .sum())
+}
+
+/// check a share of a Feldman sharing against the commitments `G * a_j` to
+/// the polynomial: `G * share == sum(commitments[j] * index^j)`. a share never
+/// matches an empty commitment vector.
+pub fn verify_share(index: &Scalar, share: &Scalar, commitments: &[Point]) -> bool {
+    if commitments.is_empty() {
+        return false;
+    }
+    let powers: Vec<Scalar> = (0..commitments.len())
+        .map(|j| index.pow(j as u32))
+        .collect();
+    Point::multi_scalar_mul(commitments, &powers) == Point::from_scalar(share)
+}
+
+/// interpolation at zero in the exponent: from shares `(x, G * f(x))`, the
+/// point `G * f(0)`, with the same indices as `lagrange_interpolate_at_zero`
+pub fn lagrange_interpolate_points_at_zero(shares: &[(Scalar, Point)]) -> Result<Point, PvssError> {
+    if shares.is_empty() {
+        return Err(PvssError::NotEnoughShares);
+    }
+    let indices: Vec<Scalar> = shares.iter().map(|(x, _)| x.clone()).collect();
+    let points: Vec<Point> = shares.iter().map(|(_, p)| p.clone()).collect();
+    let coefficients = lagrange_coefficients(&indices, &Scalar::zero())?;
+    Ok(Point::multi_scalar_mul(&points, &coefficients))
+}
+
+/// the Lagrange coefficients `l_i` of the indices at `at`, such that
+/// `sum(l_i * f(indices[i])) == f(at)` for every polynomial `f` of degree
+/// below `indices.len()`
+pub fn lagrange_coefficients(indices: &[Scalar], at: &Scalar) -> Result<Vec<Scalar>, PvssError> {
+    let mut numerators = Vec::with_capacity(indices.len());
+    let mut denominators = Vec::with_capacity(indices.len());
+    for (i, xi) in indices.iter().enumerate() {
+        let mut numerator = Scalar::multiplicative_identity();
+        let mut denominator = Scalar::multiplicative_identity();
+        for (j, xj) in indices.iter().enumerate() {
+            if i != j {
+                numerator *= at - xj;
+                denominator *= xi - xj;
+            }
+        }
+        if denominator.is_zero() {
+            return Err(PvssError::DuplicateIndex);
+        }
+        numerators.push(numerator);
+        denominators.push(denominator);
+    }
+    Ok(numerators
+        .into_iter()
+        .zip(Scalar::batch_inverse(&denominators))
+        .map(|(n, d)| n * d)
+        .collect())
+}
@@ -3,6 +3,7 @@
 
 use super::dleq;
 use super::math;
+use super::sharing::participant_index;
 use super::types::*;
 
 use super::crypto::*;
@@ -45,14 +46,7 @@ pub fn escrow(t: Threshold) -> Escrow {
     let secret = poly.at_zero();
     let g_s = Point::from_scalar(&secret);
 
-    let challenge = Scalar::generate();
-    let dleq = dleq::DLEQ {
-        g1: Point::generator(),
-        h1: g_s.clone(),
-        g2: gen.clone(),
-        h2: gen.mul(&secret),
-    };
-    let proof = dleq::Proof::create(challenge, secret, dleq);
+    let proof = dleq::Proof::create(&secret, &Point::generator(), &g_s, &gen, &gen.mul(&secret));
 
     Escrow {
         extra_generator: gen,
@@ -75,19 +69,10 @@ pub fn commitments(escrow: &Escrow) -> Vec<Commitment> {
 }
 
 pub fn create_share(escrow: &Escrow, share_id: ShareId, public: &PublicKey) -> EncryptedShare {
-    assert!(share_id != 0, "trying to create a share with id = 0");
-
-    let peval = escrow.polynomial.evaluate(Scalar::from_u32(share_id));
-    let challenge = Scalar::generate();
+    let peval = escrow.polynomial.evaluate(participant_index(share_id));
     let xi = escrow.extra_generator.mul(&peval);
     let yi = public.point.mul(&peval);
-    let dleq = dleq::DLEQ {
-        g1: escrow.extra_generator.clone(),
-        h1: xi,
-        g2: public.point.clone(),
-        h2: yi.clone(),
-    };
-    let proof = dleq::Proof::create(challenge, peval, dleq);
+    let proof = dleq::Proof::create(&peval, &escrow.extra_generator, &xi, &public.point, &yi);
     EncryptedShare {
         id: share_id,
         encrypted_val: yi,
@@ -107,12 +92,10 @@ where
 }
 
 fn create_xi(id: ShareId, commitments: &[Commitment]) -> Point {
-    let mut r = Point::infinity();
-    for (j, com) in commitments.iter().enumerate() {
-        let e = Scalar::from_u32(id).pow(j as u32);
-        r = r.clone() + (com.point.mul(&e));
-    }
-    r
+    let x = participant_index(id);
+    let points: Vec<Point> = commitments.iter().map(|c| c.point.clone()).collect();
+    let scalars: Vec<Scalar> = (0..commitments.len()).map(|j| x.pow(j as u32)).collect();
+    Point::multi_scalar_mul(&points, &scalars)
 }
 
 impl EncryptedShare {
@@ -123,26 +106,22 @@ impl EncryptedShare {
         extra_generator: &Point,
         commitments: &[Commitment],
     ) -> bool {
+        if id == 0 {
+            return false;
+        }
         let xi = create_xi(id, commitments);
-        let dleq = dleq::DLEQ {
-            g1: extra_generator.clone(),
-            h1: xi,
-            g2: public.point.clone(),
-            h2: self.encrypted_val.clone(),
-        };
-        self.proof.verify(dleq)
+        self.proof.verify(extra_generator, &xi, &public.point, &self.encrypted_val)
     }
 }
 
 impl DecryptedShare {
     pub fn verify(&self, public: &PublicKey, eshare: &EncryptedShare) -> bool {
-        let dleq = dleq::DLEQ {
-            g1: Point::generator(),
-            h1: public.point.clone(),
-            g2: self.decrypted_val.clone(),
-            h2: eshare.encrypted_val.clone(),
-        };
-        self.proof.verify(dleq)
+        self.proof.verify(
+            &Point::generator(),
+            &public.point,
+            &self.decrypted_val,
+            &eshare.encrypted_val,
+        )
     }
 }
 
@@ -151,19 +130,12 @@ pub fn decrypt_share(
     public: &PublicKey,
     share: &EncryptedShare,
 ) -> DecryptedShare {
-    let challenge = Scalar::generate();
     let xi = private.scalar.clone();
     let yi = public.point.clone();
     let lifted_yi = share.encrypted_val.clone();
     let xi_inverse = xi.inverse();
     let si = lifted_yi.mul(&xi_inverse);
-    let dleq = dleq::DLEQ {
-        g1: Point::generator(),
-        h1: yi,
-        g2: si.clone(),
-        h2: lifted_yi,
-    };
-    let proof = dleq::Proof::create(challenge, xi, dleq);
+    let proof = dleq::Proof::create(&xi, &Point::generator(), &yi, &si, &lifted_yi);
     DecryptedShare {
         id: share.id,
         decrypted_val: si,
@@ -175,28 +147,29 @@ fn interpolate_one(t: Threshold, sid: usize, shares: &[DecryptedShare]) -> Scala
     let mut v = Scalar::from_u32(1);
     for j in 0..(t as usize) {
         if j != sid {
-            let sj = Scalar::from_u32(shares[j].id);
-            let si = Scalar::from_u32(shares[sid].id);
-            let d = sj.clone() - si;
-            let dinv = d.inverse();
-            let e = sj * dinv;
-            v = v * e;
+            let sj = participant_index(shares[j].id);
+            let si = participant_index(shares[sid].id);
+            let d = &sj - &si;
+            v *= sj / d;
         }
     }
     v
 }
 
 // Try to recover a secret
+#[allow(clippy::result_unit_err)]
 pub fn recover(t: Threshold, shares: &[DecryptedShare]) -> Result<Secret, ()> {
-    if t as usize > shares.len() {
+    if t as usize > shares.len() || shares.iter().any(|s| s.id == 0) {
         return Err(());
     };
-    let mut result = Point::infinity();
-    for i in 0..(t as usize) {
-        let v = interpolate_one(t, i, shares);
-        result = result + shares[i].decrypted_val.mul(&v);
-    }
-    Ok(result)
+    let points: Vec<Point> = shares[..t as usize]
+        .iter()
+        .map(|s| s.decrypted_val.clone())
+        .collect();
+    let scalars: Vec<Scalar> = (0..(t as usize))
+        .map(|i| interpolate_one(t, i, shares))
+        .collect();
+    Ok(Point::multi_scalar_mul(&points, &scalars))
 }
 
 pub fn verify_secret(
@@ -205,11 +178,5 @@ pub fn verify_secret(
     commitments: &[Commitment],
     proof: dleq::Proof,
 ) -> bool {
-    let dleq = dleq::DLEQ {
-        g1: Point::generator(),
-        h1: secret,
-        g2: extra_generator,
-        h2: commitments[0].point.clone(),
-    };
-    proof.verify(dleq)
+    proof.verify(&Point::generator(), &secret, &extra_generator, &commitments[0].point)
 }
@@ -0,0 +1,61 @@
+// Fiat-Shamir transcripts: everything a proof commits to is absorbed in
+// order after a label naming the proof, and the challenge is a hash of it all.
+// every value is absorbed under a label naming its role, so proofs of
+// different kinds, or the same points in different roles, never share a
+// challenge.
+use alloc::vec::Vec;
+
+use super::crypto::{Point, Scalar};
+
+const TRANSCRIPT_DST: &[u8] = b"PVSS-BLS12381-TRANSCRIPT-V1";
+
+#[derive(Clone)]
+pub struct Transcript {
+    data: Vec<u8>,
+}
+
+impl Transcript {
+    /// a transcript for the proofs named `label`, e.g. `b"dleq"`
+    pub fn new(label: &[u8]) -> Transcript {
+        let mut t = Transcript { data: Vec::new() };
+        t.append_bytes(label);
+        t
+    }
+
+    // prefixed by their length, so that the boundaries between items are
+    // part of the hash
+    fn append_bytes(&mut self, bytes: &[u8]) {
+        self.data.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+        self.data.extend_from_slice(bytes);
+    }
+
+    /// absorb an encoded value, such as a proof
+    pub fn append_message(&mut self, label: &[u8], bytes: &[u8]) {
+        self.append_bytes(label);
+        self.append_bytes(bytes);
+    }
+
+    pub fn append_point(&mut self, label: &[u8], p: &Point) {
+        self.append_message(label, &p.to_bytes_compressed());
+    }
+
+    /// `append_point` of each point under the same label
+    pub fn append_points(&mut self, label: &[u8], points: &[Point]) {
+        for p in points {
+            self.append_point(label, p);
+        }
+    }
+
+    pub fn append_scalar(&mut self, label: &[u8], s: &Scalar) {
+        self.append_message(label, &s.to_bytes_canonical());
+    }
+
+    /// the challenge for everything absorbed so far. it is absorbed in turn,
+    /// so that a second challenge differs from the first.
+    pub fn challenge_scalar(&mut self, label: &[u8]) -> Scalar {
+        self.append_bytes(label);
+        let c = Scalar::hash_to_scalar(&self.data, TRANSCRIPT_DST);
+        self.append_bytes(&c.to_bytes_canonical());
+        c
+    }
+}